@@ -1,65 +1,48 @@
+use advent_of_code::ParseError;
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|line| {
-            let filtered_string = line
-                .chars()
-                .filter(|char| char.is_ascii_digit())
-                .collect::<String>();
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1), ("2", 2), ("3", 3), ("4", 4), ("5", 5), ("6", 6), ("7", 7), ("8", 8), ("9", 9)];
 
-            let first = filtered_string.chars().next().unwrap();
-            let last = filtered_string.chars().last().unwrap();
+const WORDS: [(&str, u32); 9] = [
+    ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6), ("seven", 7), ("eight", 8), ("nine", 9)];
 
-            [first, last]
-                .iter()
-                .collect::<String>()
-                .parse::<u32>()
-                .unwrap()
-        }
-        )
-        .sum::<u32>()
-        .into()
-}
+fn parse(input: &str, with_words: bool) -> Result<Vec<u32>, ParseError> {
+    let numbers: Vec<(&str, u32)> = match with_words {
+        true => DIGITS.iter().chain(WORDS.iter()).copied().collect(),
+        false => DIGITS.to_vec(),
+    };
 
-pub fn part_two(input: &str) -> Option<u32> {
     input
         .lines()
-        .map(convert)
-        .sum::<u32>()
-        .into()
+        .map(|line| convert(line, &numbers).ok_or_else(|| ParseError::at(input, line, "no digit found")))
+        .collect()
 }
 
-fn convert(line: &str) -> u32 {
-    let numbers = [
-        ("one", 1), ("1", 1),
-        ("two", 2), ("2", 2),
-        ("three", 3), ("3", 3),
-        ("four", 4), ("4", 4),
-        ("five", 5), ("5", 5),
-        ("six", 6), ("6", 6),
-        ("seven", 7), ("7", 7),
-        ("eight", 8), ("8", 8),
-        ("nine", 9), ("9", 9)];
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input, false)?.iter().sum())
+}
 
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    Ok(parse(input, true)?.iter().sum())
+}
+
+fn convert(line: &str, numbers: &[(&str, u32)]) -> Option<u32> {
     let min = numbers
         .iter()
         .map(|number| (line.find(number.0), number.1))
         .filter(|t| t.0.is_some())
-        .min_by_key(|t| t.0)
-        .unwrap()
+        .min_by_key(|t| t.0)?
         .1;
 
     let max = numbers
         .iter()
         .map(|number| (line.rfind(number.0), number.1))
         .filter(|t| t.0.is_some())
-        .max_by_key(|t| t.0)
-        .unwrap()
+        .max_by_key(|t| t.0)?
         .1;
 
-    min * 10 + max
+    Some(min * 10 + max)
 }
 
 #[cfg(test)]
@@ -69,12 +52,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(142));
+        assert_eq!(result.unwrap(), 142);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(44));
+        assert_eq!(result.unwrap(), 44);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            1abc2\n\
+            pqr3stu8vwx\n\
+            two1nine\n\
+            xtwone3four");
+        advent_of_code::template::fuzz::check_parser(&input, |input| parse(input, false));
+        advent_of_code::template::fuzz::check_parser(&input, |input| parse(input, true));
     }
 }
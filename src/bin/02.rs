@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(2);
 
 #[derive(Eq, PartialEq, Hash)]
@@ -13,42 +14,56 @@ struct Game {
     draws: HashMap<usize, Vec<ColorDraw>>,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = input.lines().map(|line| {
-        let mut parts = line.split(':');
-
-        let game_id = parts
-            .next()
-            .unwrap()
-            .split(' ')
-            .last()
-            .unwrap()
-            .parse::<u32>()
-            .unwrap();
-
-        let draw_parts = parts.last().unwrap().trim().split(';');
-
-        let mut all_draws: HashMap<usize, Vec<ColorDraw>> = HashMap::new();
-
-        draw_parts.enumerate().for_each(|(index, draw_part)| {
-            let draws: Vec<ColorDraw> = draw_part.trim().split(',').map(|draw| {
-                let mut draw_pair = draw.trim().split(' ');
-                match draw_pair.clone().last() {
-                    Some("red") => ColorDraw::Red { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    Some("blue") => ColorDraw::Blue { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    Some("green") => ColorDraw::Green { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    _ => panic!("invalid color")
-                }
-            }).collect();
-
-            all_draws.insert(index, draws);
-        });
+impl ColorDraw {
+    fn parse(line: &str, s: &str) -> Result<Self, ParseError> {
+        let Some((amount_str, color)) = s.split_once(' ') else {
+            return Err(ParseError::at(line, s, "expected an amount and a color separated by a space"));
+        };
+
+        let amount = parse_at(line, amount_str)?;
 
-        Game {
-            id: game_id,
-            draws: all_draws,
+        match color {
+            "red" => Ok(ColorDraw::Red { amount }),
+            "blue" => Ok(ColorDraw::Blue { amount }),
+            "green" => Ok(ColorDraw::Green { amount }),
+            _ => Err(ParseError::at(line, color, format!("invalid color {color:?}"))),
         }
-    });
+    }
+}
+
+impl Game {
+    fn parse(s: &str) -> Result<Self, ParseError> {
+        let Some((game_part, draws_part)) = s.split_once(':') else {
+            return Err(ParseError::at(s, s, "expected a game id and draws separated by ':'"));
+        };
+
+        let id_str = game_part.rsplit(' ').next().unwrap_or(game_part);
+        let id = parse_at(s, id_str)?;
+
+        let mut draws: HashMap<usize, Vec<ColorDraw>> = HashMap::new();
+
+        for (index, draw_part) in draws_part.split(';').enumerate() {
+            let draw = draw_part
+                .split(',')
+                .map(|draw| ColorDraw::parse(s, draw.trim()))
+                .collect::<Result<_, _>>()?;
+
+            draws.insert(index, draw);
+        }
+
+        Ok(Game { id, draws })
+    }
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .map(|line| Game::parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let games = parse_games(input)?.into_iter();
 
     let valid_games = games.filter(|game| game.draws.iter().all(|(_, draws)| draws.iter().all(|draw| match draw {
         ColorDraw::Red { amount } => amount <= &12,
@@ -56,47 +71,13 @@ pub fn part_one(input: &str) -> Option<u32> {
         ColorDraw::Green { amount } => amount <= &13,
     })));
 
-    Some(valid_games.map(|game| game.id).sum())
+    Ok(valid_games.map(|game| game.id).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = input.lines().map(|line| {
-        let mut parts = line.split(':');
-
-        let game_id = parts
-            .next()
-            .unwrap()
-            .split(' ')
-            .last()
-            .unwrap()
-            .parse::<u32>()
-            .unwrap();
-
-        let draw_parts = parts.last().unwrap().trim().split(';');
-
-        let mut all_draws: HashMap<usize, Vec<ColorDraw>> = HashMap::new();
-
-        draw_parts.enumerate().for_each(|(index, draw_part)| {
-            let draws: Vec<ColorDraw> = draw_part.trim().split(',').map(|draw| {
-                let mut draw_pair = draw.trim().split(' ');
-                match draw_pair.clone().last() {
-                    Some("red") => ColorDraw::Red { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    Some("blue") => ColorDraw::Blue { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    Some("green") => ColorDraw::Green { amount: draw_pair.next().unwrap().parse::<u32>().unwrap() },
-                    _ => panic!("invalid color")
-                }
-            }).collect();
-
-            all_draws.insert(index, draws);
-        });
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let games = parse_games(input)?.into_iter();
 
-        Game {
-            id: game_id,
-            draws: all_draws,
-        }
-    });
-
-    Some(games.map(|game| {
+    Ok(games.map(|game| {
         let draws = game.draws.values().flatten();
 
         let mut max_red: u32 = 0;
@@ -122,12 +103,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result.unwrap(), 8);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        assert_eq!(result.unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue");
+        advent_of_code::template::fuzz::check_parser(&input, parse_games);
     }
 }
@@ -1,12 +1,18 @@
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(3);
 
 #[derive(Debug)]
 struct Number {
     value_str: String,
+    value: u32,
     position: Position,
 }
 
 impl Number {
+    fn parse(input: &str, s: &str, position: Position) -> Result<Self, ParseError> {
+        Ok(Number { value_str: s.into(), value: parse_at(input, s)?, position })
+    }
+
     fn is_part(&self, symbols: &[Symbol]) -> bool {
         for index in (0..self.value_str.len()).collect::<Vec<usize>>() {
             let possible_collisions: Vec<Position> = vec![
@@ -31,7 +37,7 @@ impl Number {
     }
 
     fn to_number(&self) -> u32 {
-        self.value_str.parse::<u32>().unwrap()
+        self.value
     }
 
     fn get_positions(&self) -> Vec<Position> {
@@ -83,75 +89,58 @@ struct Position {
     y: i32,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+}
+
+fn parse(input: &str) -> Result<Schematic, ParseError> {
     let mut numbers: Vec<Number> = Vec::new();
     let mut symbols: Vec<Symbol> = Vec::new();
 
     for (line_index, line) in input.lines().enumerate() {
-        let mut curr_number_str: String = String::new();
-        let mut curr_number_pos: Position = Position { x: 0, y: line_index as i32 };
+        let mut curr_number_start: Option<(usize, Position)> = None;
+
+        for (char_index, (byte_index, char)) in line.char_indices().enumerate() {
+            let position = Position { x: char_index as i32, y: line_index as i32 };
 
-        for (char_index, char) in line.chars().enumerate() {
             if char.is_ascii_digit() {
-                curr_number_str.push(char);
-            } else {
-                if char != '.' {
-                    symbols.push(Symbol { is_gear: char == '*', position: Position { x: char_index as i32, y: line_index as i32 } });
-                }
-
-                if !curr_number_str.is_empty() {
-                    numbers.push(Number { value_str: curr_number_str.clone(), position: curr_number_pos });
-                    curr_number_str = String::new();
-                }
-
-                curr_number_pos = Position { x: char_index as i32 + 1, y: line_index as i32 };
+                curr_number_start.get_or_insert((byte_index, position));
+                continue;
+            }
+
+            if char != '.' {
+                symbols.push(Symbol { is_gear: char == '*', position });
+            }
+
+            if let Some((start, number_pos)) = curr_number_start.take() {
+                numbers.push(Number::parse(input, &line[start..byte_index], number_pos)?);
             }
         }
 
-        if !curr_number_str.is_empty() {
-            numbers.push(Number { value_str: curr_number_str.clone(), position: curr_number_pos });
+        if let Some((start, number_pos)) = curr_number_start {
+            numbers.push(Number::parse(input, &line[start..], number_pos)?);
         }
     }
 
-    let part_numbers = numbers.iter().filter(|number| number.is_part(&symbols));
-
-    Some(part_numbers.map(|part_number| part_number.to_number()).sum())
+    Ok(Schematic { numbers, symbols })
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut numbers: Vec<Number> = Vec::new();
-    let mut symbols: Vec<Symbol> = Vec::new();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let Schematic { numbers, symbols } = parse(input)?;
 
-    for (line_index, line) in input.lines().enumerate() {
-        let mut curr_number_str: String = String::new();
-        let mut curr_number_pos: Position = Position { x: 0, y: line_index as i32 };
+    let part_numbers = numbers.iter().filter(|number| number.is_part(&symbols));
 
-        for (char_index, char) in line.chars().enumerate() {
-            if char.is_ascii_digit() {
-                curr_number_str.push(char);
-            } else {
-                if char != '.' {
-                    symbols.push(Symbol { is_gear: char == '*', position: Position { x: char_index as i32, y: line_index as i32 } });
-                }
-
-                if !curr_number_str.is_empty() {
-                    numbers.push(Number { value_str: curr_number_str.clone(), position: curr_number_pos });
-                    curr_number_str = String::new();
-                }
-
-                curr_number_pos = Position { x: char_index as i32 + 1, y: line_index as i32 };
-            }
-        }
+    Ok(part_numbers.map(|part_number| part_number.to_number()).sum())
+}
 
-        if !curr_number_str.is_empty() {
-            numbers.push(Number { value_str: curr_number_str.clone(), position: curr_number_pos });
-        }
-    }
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let Schematic { numbers, symbols } = parse(input)?;
 
     let part_numbers: Vec<&Number> = numbers.iter().filter(|number| number.is_part(&symbols)).collect();
     let gears = symbols.iter().filter(|symbol| symbol.is_gear);
 
-    Some(gears.filter_map(|gear| gear.calculate_gear_ratio(&part_numbers)).sum())
+    Ok(gears.filter_map(|gear| gear.calculate_gear_ratio(&part_numbers)).sum())
 }
 
 #[cfg(test)]
@@ -161,12 +150,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
+        assert_eq!(result.unwrap(), 4361);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
+        assert_eq!(result.unwrap(), 467835);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            467..114..\n\
+            ...*......\n\
+            ..35..633.\n\
+            ......#...");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
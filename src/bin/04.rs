@@ -1,26 +1,24 @@
 use regex::Regex;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(4);
 
 #[derive(Debug)]
 struct Game {
-    id: u32,
     winning_numbers: Vec<u32>,
     own_numbers: Vec<u32>,
     instances: u32,
 }
 
 impl Game {
-    fn create(line: &str) -> Game {
-        let whitespace_regex = Regex::new(r"\s+").unwrap();
-        let normalized_line = whitespace_regex.replace_all(line, " ").into_owned();
-
-        let game_regex = Regex::new(r"Card (?<game_id>\d+):(?<winning_numbers>[ \d]+)\|(?<own_numbers>[ \d]+)").unwrap();
-        let Some((_, [game_id_str, winning_numbers_str, own_numbers_str])) = game_regex.captures(&normalized_line).map(|caps| caps.extract()) else { panic!("invalid line") };
-
-        let game_id = game_id_str.trim().parse::<u32>().unwrap();
-        let winning_numbers = winning_numbers_str.trim().split(' ').map(|x| x.parse::<u32>().unwrap()).collect();
-        let own_numbers = own_numbers_str.trim().split(' ').map(|x| x.parse::<u32>().unwrap()).collect();
-        Game { id: game_id, winning_numbers, own_numbers, instances: 1 }
+    fn parse(line: &str) -> Result<Game, ParseError> {
+        let game_regex = Regex::new(r"Card\s+(?<game_id>\d+):(?<winning_numbers>[ \d]+)\|(?<own_numbers>[ \d]+)").unwrap();
+        let Some((_, [_game_id_str, winning_numbers_str, own_numbers_str])) = game_regex.captures(line).map(|caps| caps.extract()) else {
+            return Err(ParseError::at(line, line, "expected \"Card <id>: <winning numbers> | <own numbers>\""));
+        };
+
+        let winning_numbers = winning_numbers_str.split_whitespace().map(|x| parse_at(line, x)).collect::<Result<_, _>>()?;
+        let own_numbers = own_numbers_str.split_whitespace().map(|x| parse_at(line, x)).collect::<Result<_, _>>()?;
+        Ok(Game { winning_numbers, own_numbers, instances: 1 })
     }
 
     fn increase_instances(&mut self) {
@@ -28,31 +26,34 @@ impl Game {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = input
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    input
         .lines()
-        .map(Game::create);
+        .map(|line| Game::parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let games = parse_games(input)?;
 
     let sum = games
+        .iter()
         .map(|game| {
             let matching_numbers: Vec<&u32> = game.winning_numbers.iter().filter(|winning_number| game.own_numbers.contains(winning_number)).collect();
 
-            return match matching_numbers[..] {
+            match matching_numbers[..] {
                 [] => 0,
                 [_] => 1,
                 _ => matching_numbers[1..].iter().fold(1, |acc, _| acc * 2)
-            };
+            }
         })
         .sum();
 
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut games: Vec<Game> = input
-        .lines()
-        .map(Game::create)
-        .collect();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let mut games = parse_games(input)?;
 
     for index in 0..games.len() {
         for _ in 0..games[index].instances {
@@ -66,7 +67,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let card_count = games.iter().map(|game| game.instances).sum();
 
-    Some(card_count)
+    Ok(card_count)
 }
 
 #[cfg(test)]
@@ -76,12 +77,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result.unwrap(), 13);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result.unwrap(), 30);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+            Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19");
+        advent_of_code::template::fuzz::check_parser(&input, parse_games);
     }
 }
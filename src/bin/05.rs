@@ -1,5 +1,6 @@
 use rayon::prelude::*;
-advent_of_code::solution!(5);
+use advent_of_code::{parse_at, Interval, IntervalSet, ParseError};
+advent_of_code::solution!(5, [part_one], [part_two, part_two_brute_force]);
 
 #[derive(Debug)]
 struct MappingGroup {
@@ -19,6 +20,23 @@ impl MappingGroup {
             }
         }
     }
+
+    fn get_mapped_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut mapped = IntervalSet::new();
+        let mut unmapped = ranges.clone();
+
+        for m in &self.mapping_values {
+            let src = Interval::new(m.src_range_start, m.src_range_start + m.range_length);
+
+            for overlap in unmapped.iter().filter_map(|range| range.intersection(&src)) {
+                mapped.insert(overlap.translate(m.src_range_start, m.dest_range_start));
+            }
+
+            unmapped.remove(src);
+        }
+
+        mapped.union(&unmapped)
+    }
 }
 
 #[derive(Debug)]
@@ -28,37 +46,59 @@ struct MappingValues {
     range_length: u64,
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
+impl MappingValues {
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let map_values: Vec<u64> = line.split_whitespace().map(|x| parse_at(input, x)).collect::<Result<_, _>>()?;
 
-    let initial_seeds: Vec<u64> = lines
-        .next().unwrap()
-        .split(':')
-        .last().unwrap()
-        .trim()
-        .split(' ')
-        .map(|seed_str| seed_str.parse::<u64>().unwrap())
-        .collect();
+        let [dest_range_start, src_range_start, range_length] = map_values[..] else {
+            return Err(ParseError::at(input, line, "invalid amount of mapping values"));
+        };
 
-    let mapping_str_block = input
-        .split("\n\n")
-        .skip(1);
+        if dest_range_start.checked_add(range_length).is_none() || src_range_start.checked_add(range_length).is_none() {
+            return Err(ParseError::at(input, line, "mapping range exceeds the value range"));
+        }
 
-    let mapping_groups: Vec<MappingGroup> = mapping_str_block
+        Ok(MappingValues { dest_range_start, src_range_start, range_length })
+    }
+}
+
+#[derive(Debug)]
+struct Almanac {
+    seeds: Vec<u64>,
+    mapping_groups: Vec<MappingGroup>,
+}
+
+fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let mut blocks = input.split("\n\n");
+
+    let seeds_line = blocks.next().unwrap_or_default();
+    let Some((_, seeds_str)) = seeds_line.split_once(':') else {
+        return Err(ParseError::at(input, seeds_line, "expected \"seeds: <numbers>\""));
+    };
+
+    let seeds: Vec<u64> = seeds_str.split_whitespace().map(|x| parse_at(input, x)).collect::<Result<_, _>>()?;
+
+    if seeds.is_empty() {
+        return Err(ParseError::at(input, seeds_line, "no seeds found"));
+    }
+
+    let mapping_groups = blocks
         .map(|block| {
             let mappings = block
                 .lines()
                 .skip(1)
-                .map(|line| {
-                    let map_values: Vec<u64> = line.split(' ').map(|x| x.parse::<u64>().unwrap()).collect();
-                    if map_values.len() != 3 { panic!("invalid amount of mapping values") };
-                    MappingValues { dest_range_start: map_values[0], src_range_start: map_values[1], range_length: map_values[2] }
-                })
-                .collect();
-
-            MappingGroup { mapping_values: mappings }
+                .map(|line| MappingValues::parse(input, line))
+                .collect::<Result<_, _>>()?;
+
+            Ok(MappingGroup { mapping_values: mappings })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
+
+    Ok(Almanac { seeds, mapping_groups })
+}
+
+pub fn part_one(input: &str) -> Result<u64, ParseError> {
+    let Almanac { seeds: initial_seeds, mapping_groups } = parse(input)?;
 
     let results = initial_seeds.iter().map(|initial_seed| {
         let mut curr_value: u64 = *initial_seed;
@@ -70,44 +110,33 @@ pub fn part_one(input: &str) -> Option<u64> {
         (*initial_seed, curr_value)
     });
 
-    Some(results.min_by_key(|x| x.1).unwrap().1)
+    Ok(results.min_by_key(|x| x.1).unwrap().1)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut lines = input.lines();
-
-    let initial_seeds: Vec<u64> = lines
-        .next().unwrap()
-        .split(':')
-        .last().unwrap()
-        .trim()
-        .split(' ')
-        .map(|seed_str| seed_str.parse::<u64>().unwrap())
-        .collect::<Vec<u64>>()
-        .chunks(2)
-        .flat_map(|chunk| {
-            (0..chunk[1]).map(|i| chunk[0] + i)
-        })
-        .collect();
+fn seed_ranges(seeds: &[u64]) -> Result<IntervalSet<u64>, ParseError> {
+    if !seeds.len().is_multiple_of(2) || seeds.chunks(2).any(|chunk| chunk[0].checked_add(chunk[1]).is_none()) {
+        return Err(ParseError::new("expected seed ranges as pairs of start and length"));
+    }
 
-    let mapping_str_block = input
-        .split("\n\n")
-        .skip(1);
+    Ok(seeds.chunks(2).map(|chunk| Interval::new(chunk[0], chunk[0] + chunk[1])).collect())
+}
 
-    let mapping_groups: Vec<MappingGroup> = mapping_str_block
-        .map(|block| {
-            let mappings = block
-                .lines()
-                .skip(1)
-                .map(|line| {
-                    let map_values: Vec<u64> = line.split(' ').map(|x| x.parse::<u64>().unwrap()).collect();
-                    if map_values.len() != 3 { panic!("invalid amount of mapping values") };
-                    MappingValues { dest_range_start: map_values[0], src_range_start: map_values[1], range_length: map_values[2] }
-                })
-                .collect();
-
-            MappingGroup { mapping_values: mappings }
-        })
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let Almanac { seeds, mapping_groups } = parse(input)?;
+
+    let ranges = mapping_groups
+        .iter()
+        .fold(seed_ranges(&seeds)?, |ranges, mapping_group| mapping_group.get_mapped_ranges(&ranges));
+
+    ranges.first().ok_or_else(|| ParseError::new("no seeds found"))
+}
+
+pub fn part_two_brute_force(input: &str) -> Result<u64, ParseError> {
+    let Almanac { seeds, mapping_groups } = parse(input)?;
+
+    let initial_seeds: Vec<u64> = seed_ranges(&seeds)?
+        .iter()
+        .flat_map(|range| range.start..range.end)
         .collect();
 
     let results = initial_seeds.into_par_iter().map(|initial_seed| {
@@ -120,7 +149,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         (initial_seed, curr_value)
     });
 
-    Some(results.min_by_key(|x| x.1).unwrap().1)
+    results.min_by_key(|x| x.1).map(|x| x.1).ok_or_else(|| ParseError::new("no seeds found"))
 }
 
 #[cfg(test)]
@@ -130,12 +159,33 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        assert_eq!(result.unwrap(), 35);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result.unwrap(), 46);
+    }
+
+    #[test]
+    fn test_part_two_brute_force() {
+        let result = part_two_brute_force(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result.unwrap(), 46);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            seeds: 79 14 55 13\n\
+            \n\
+            seed-to-soil map:\n\
+            50 98 2\n\
+            52 50 48\n\
+            \n\
+            soil-to-fertilizer map:\n\
+            0 15 37\n\
+            37 52 2");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
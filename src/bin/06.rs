@@ -1,30 +1,37 @@
-use regex::Regex;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(6);
 
-fn parse_races(input: &str) -> Vec<(u32, u32)> {
-    let whitespace_regex = Regex::new(r"\s+").unwrap();
-    let numbers: Vec<Vec<u32>> = input.lines().map(|line| {
-        let numbers_str = line.split(':').last().unwrap().trim();
-        let normalized_numbers_str = whitespace_regex.replace_all(numbers_str, " ").into_owned();
-        normalized_numbers_str.split(' ').map(|number_str| number_str.parse::<u32>().unwrap()).collect()
-    }).collect();
+fn split_lines(input: &str) -> Result<(&str, &str), ParseError> {
+    let numbers: Vec<&str> = input.lines().map(|line| {
+        line.split_once(':').map(|(_, numbers_str)| numbers_str).ok_or_else(|| ParseError::at(input, line, "expected a label and numbers separated by ':'"))
+    }).collect::<Result<_, _>>()?;
 
-    let times = numbers.first().unwrap().iter();
-    let distances = numbers.last().unwrap().iter();
+    match numbers[..] {
+        [times, distances] => Ok((times, distances)),
+        _ => Err(ParseError::new("expected a line of times and a line of distances")),
+    }
+}
+
+fn parse_races(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    let (times_str, distances_str) = split_lines(input)?;
+
+    let times: Vec<u32> = times_str.split_whitespace().map(|x| parse_at(input, x)).collect::<Result<_, _>>()?;
+    let distances: Vec<u32> = distances_str.split_whitespace().map(|x| parse_at(input, x)).collect::<Result<_, _>>()?;
 
-    times.cloned().zip(distances.cloned()).collect()
+    Ok(times.into_iter().zip(distances).collect())
 }
 
-fn parse_races_alt(input: &str) -> (usize, usize) {
-    let numbers: Vec<usize> = input.lines().map(|line| {
-        let number_str = line.split(':').last().unwrap().replace(' ', "");
-        number_str.parse::<usize>().unwrap()
-    }).collect();
+fn parse_races_alt(input: &str) -> Result<(usize, usize), ParseError> {
+    let (time_str, distance_str) = split_lines(input)?;
 
-    let time = *numbers.first().unwrap();
-    let distance = *numbers.last().unwrap();
+    let parse_kerned = |numbers_str: &str| {
+        let number_str = numbers_str.replace(' ', "");
+        number_str.parse::<usize>().map_err(|e| {
+            ParseError::at(input, numbers_str, format!("could not parse {number_str:?}")).with_source(e)
+        })
+    };
 
-    (time, distance)
+    Ok((parse_kerned(time_str)?, parse_kerned(distance_str)?))
 }
 
 fn calc_distance(time_held: u32, time: u32) -> u32 {
@@ -37,22 +44,22 @@ fn calc_distance_alt(time_held: usize, time: usize) -> usize {
     time_held * rem_time
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let races = parse_races(input);
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let races = parse_races(input)?;
     let result = races.iter().map(|race| {
         let dist_reached = (0..=race.0).map(|time_held| calc_distance(time_held, race.0));
         dist_reached.filter(|dist| dist > &race.1).count()
     }).product::<usize>();
 
-    Some(result)
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let race = parse_races_alt(input);
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let race = parse_races_alt(input)?;
     let dist_reached = (0..=race.0).map(|time_held| calc_distance_alt(time_held, race.0));
     let result = dist_reached.filter(|dist| *dist > race.1).count();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -62,12 +69,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result.unwrap(), 288);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        assert_eq!(result.unwrap(), 71503);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            Time:      7  15   30\n\
+            Distance:  9  40  200");
+        advent_of_code::template::fuzz::check_parser(&input, parse_races);
+        advent_of_code::template::fuzz::check_parser(&input, parse_races_alt);
     }
 }
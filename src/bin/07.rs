@@ -1,9 +1,9 @@
 use std::cmp::Ordering;
-use std::str::FromStr;
 use itertools::Itertools;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
-advent_of_code::solution!(7);
+use advent_of_code::{parse_at, ParseError};
+advent_of_code::solution!(7, [part_one], [part_two, part_two_enumerate]);
 
 #[derive(Debug)]
 struct Game {
@@ -44,33 +44,25 @@ impl Ord for Game {
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
-struct ParseGameError {
-    message: String,
-}
-
-impl FromStr for Game {
-    type Err = ParseGameError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(' ').collect();
-        if parts.len() != 2 { return Err(ParseGameError { message: "invalid length of string parts".into() }); };
-        let (cards_part, bid_part) = (parts[0], parts[1]);
+/// Determines the best hand type of five cards.
+type Classifier = fn(&[CardLabel]) -> Result<HandType, ParseError>;
 
-        let cards: Vec<CardLabel> = match cards_part.chars().map(CardLabel::try_from).collect() {
-            Ok(cards) => cards,
-            Err(e) => return Err(ParseGameError { message: e.message }),
+impl Game {
+    fn parse(s: &str, with_jokers: bool, classify: Classifier) -> Result<Self, ParseError> {
+        let Some((cards_part, bid_part)) = s.split_once(' ') else {
+            return Err(ParseError::at(s, s, "expected cards and bid separated by a space"));
         };
 
-        let hand_type = match HandType::try_from(&cards) {
-            Ok(hand_type) => hand_type,
-            Err(e) => return Err(ParseGameError { message: e.message }),
-        };
+        let cards: Vec<CardLabel> = cards_part
+            .char_indices()
+            .map(|(idx, c)| {
+                let c = if with_jokers && c == 'J' { 'X' } else { c };
+                CardLabel::try_from(c).map_err(|e| e.within(s, &cards_part[idx..]))
+            })
+            .collect::<Result<_, _>>()?;
 
-        let bid = match bid_part.parse::<usize>() {
-            Ok(bid) => bid,
-            Err(_) => return Err(ParseGameError { message: "failed to parse bid".into() }),
-        };
+        let hand_type = classify(&cards).map_err(|e| e.within(s, cards_part))?;
+        let bid = parse_at(s, bid_part)?;
 
         Ok(Game {
             cards,
@@ -80,6 +72,13 @@ impl FromStr for Game {
     }
 }
 
+fn parse_games(input: &str, with_jokers: bool, classify: Classifier) -> Result<Vec<Game>, ParseError> {
+    input
+        .lines()
+        .map(|line| Game::parse(line, with_jokers, classify).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Hash, EnumIter, Copy, Clone)]
 enum CardLabel {
     Joker,
@@ -135,13 +134,8 @@ impl CardLabel {
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
-struct ParseCardLabelError {
-    message: String,
-}
-
 impl TryFrom<char> for CardLabel {
-    type Error = ParseCardLabelError;
+    type Error = ParseError;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
@@ -159,7 +153,7 @@ impl TryFrom<char> for CardLabel {
             'Q' => Ok(CardLabel::Q),
             'K' => Ok(CardLabel::K),
             'A' => Ok(CardLabel::A),
-            x => Err(ParseCardLabelError { message: format!("invalid card label {0}", x) })
+            x => Err(ParseError::new(format!("invalid card label {x:?}")))
         }
     }
 }
@@ -176,6 +170,37 @@ enum HandType {
 }
 
 impl HandType {
+    /// Classifies the hand by the sizes of its groups, jokers always join the largest group.
+    fn classify(cards: &[CardLabel]) -> Result<HandType, ParseError> {
+        if cards.len() != 5 {
+            return Err(ParseError::new(format!("expected 5 cards, found {}", cards.len())));
+        }
+
+        let jokers = cards.iter().filter(|card| card == &&CardLabel::Joker).count();
+        let mut counts: Vec<usize> = cards.iter()
+            .filter(|card| card != &&CardLabel::Joker)
+            .counts()
+            .into_values()
+            .sorted()
+            .rev()
+            .collect();
+
+        match counts.first_mut() {
+            Some(largest) => *largest += jokers,
+            None => counts.push(jokers),
+        }
+
+        Ok(match counts[..] {
+            [5] => HandType::FiveOfAKind,
+            [4, 1] => HandType::FourOfAKind,
+            [3, 2] => HandType::FullHouse,
+            [3, 1, 1] => HandType::ThreeOfAKind,
+            [2, 2, 1] => HandType::TwoPair,
+            [2, 1, 1, 1] => HandType::OnePair,
+            _ => HandType::HighCard,
+        })
+    }
+
     fn get_hand_type(cards: &[CardLabel]) -> Option<HandType> {
         if cards.iter().all_equal() {
             return Some(HandType::FiveOfAKind);
@@ -218,15 +243,13 @@ impl HandType {
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
-struct ParseHandTypeError {
-    message: String,
-}
-
-impl TryFrom<&Vec<CardLabel>> for HandType {
-    type Error = ParseHandTypeError;
+impl HandType {
+    /// Tries every replacement for the jokers and picks the best resulting hand type.
+    fn enumerate(cards: &[CardLabel]) -> Result<HandType, ParseError> {
+        if cards.len() != 5 {
+            return Err(ParseError::new(format!("expected 5 cards, found {}", cards.len())));
+        }
 
-    fn try_from(cards: &Vec<CardLabel>) -> Result<Self, Self::Error> {
         let possible_hand_types: Vec<HandType> = CardLabel::get_combinations(cards).iter()
             .filter_map(|combination| HandType::get_hand_type(combination))
             .collect();
@@ -234,41 +257,32 @@ impl TryFrom<&Vec<CardLabel>> for HandType {
         let best_hand_type = possible_hand_types.iter().max();
 
         match best_hand_type {
-            None => Err(ParseHandTypeError { message: "no hand type found".into() }),
+            None => Err(ParseError::new("no hand type found")),
             Some(hand_type) => Ok(*hand_type),
         }
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let games: Vec<Game> = input
-        .lines()
-        .map(|line| line.parse::<Game>())
-        .collect::<Result<Vec<Game>, ParseGameError>>()
-        .expect("game parsing error");
-
-    let result: usize = games.iter()
+fn total_winnings(games: &[Game]) -> usize {
+    games.iter()
         .sorted().enumerate()
         .map(|(index, game)| game.bid * (index + 1))
-        .sum();
-
-    Some(result)
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let games: Vec<Game> = input
-        .lines()
-        .map(|line| line.replace('J', "X"))
-        .map(|line| line.parse::<Game>())
-        .collect::<Result<Vec<Game>, ParseGameError>>()
-        .expect("game parsing error");
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let games = parse_games(input, false, HandType::classify)?;
+    Ok(total_winnings(&games))
+}
 
-    let result: usize = games.iter()
-        .sorted().enumerate()
-        .map(|(index, game)| game.bid * (index + 1))
-        .sum();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let games = parse_games(input, true, HandType::classify)?;
+    Ok(total_winnings(&games))
+}
 
-    Some(result)
+pub fn part_two_enumerate(input: &str) -> Result<usize, ParseError> {
+    let games = parse_games(input, true, HandType::enumerate)?;
+    Ok(total_winnings(&games))
 }
 
 #[cfg(test)]
@@ -278,12 +292,38 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
+        assert_eq!(result.unwrap(), 6440);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
+        assert_eq!(result.unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_part_two_enumerate() {
+        let result = part_two_enumerate(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result.unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_classifiers_agree() {
+        let labels = [CardLabel::Joker, CardLabel::Two, CardLabel::A];
+
+        for cards in (0..5).map(|_| labels).multi_cartesian_product() {
+            assert_eq!(HandType::classify(&cards).ok(), HandType::enumerate(&cards).ok(), "{cards:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            32T3K 765\n\
+            T55J5 684\n\
+            KTJJT 220");
+        advent_of_code::template::fuzz::check_parser(&input, |input| parse_games(input, false, HandType::classify));
+        advent_of_code::template::fuzz::check_parser(&input, |input| parse_games(input, true, HandType::classify));
+        advent_of_code::template::fuzz::check_parser(&input, |input| parse_games(input, true, HandType::enumerate));
     }
 }
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::str::FromStr;
-use itertools::Itertools;
-use num::integer::lcm;
+use advent_of_code::{ParseError, Periodic};
 advent_of_code::solution!(8);
 
 #[derive(Debug)]
@@ -17,40 +16,39 @@ struct Map {
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
 
-        let instructions_str = lines.next().unwrap();
+        let instructions_str = lines.next().unwrap_or_default();
 
-        let nodes: HashMap<String, Node> = lines.filter(|line| !line.is_empty()).map(|line| {
-            let (node_str, child_nodes_str) = line.split('=').map(|x| x.trim()).tuples().next().unwrap();
+        let instructions: Vec<Instruction> = instructions_str.char_indices().map(|(idx, c)| match c {
+            'R' => Ok(Instruction::Right),
+            'L' => Ok(Instruction::Left),
+            _ => Err(ParseError::at(s, &instructions_str[idx..], format!("invalid instruction {c:?}"))),
+        }).collect::<Result<_, _>>()?;
 
-            let child_nodes_str_cleaned = child_nodes_str.replace(['(', ')'], "");
-            let (left_child_str, right_child_str) = child_nodes_str_cleaned.split(',').map(|x| x.trim()).tuples().next().unwrap();
+        if instructions.is_empty() {
+            return Err(ParseError::new("no instructions found"));
+        }
 
-            (node_str.into(), Node {
-                id: node_str.into(),
-                left_id: left_child_str.into(),
-                right_id: right_child_str.into(),
-            })
-        }).collect();
+        let nodes: HashMap<String, Node> = lines.filter(|line| !line.is_empty()).map(|line| {
+            let node = Node::parse(line).map_err(|e| e.within(s, line))?;
+            Ok((node.id.clone(), node))
+        }).collect::<Result<_, ParseError>>()?;
 
-        let instructions = instructions_str.chars().map(|c| match c {
-            'R' => Instruction::Right,
-            'L' => Instruction::Left,
-            _ => panic!("invalid instruction"),
-        });
+        if let Some(child_id) = nodes.values().flat_map(|node| [&node.left_id, &node.right_id]).find(|id| !nodes.contains_key(*id)) {
+            return Err(ParseError::new(format!("unknown node {child_id:?}")));
+        }
 
         Ok(Map {
-            instructions: instructions.collect(),
+            instructions,
             nodes,
         })
     }
 }
 
-
 #[derive(Debug)]
 struct Node {
     id: String,
@@ -58,10 +56,32 @@ struct Node {
     right_id: String,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map: Map = input.parse().unwrap();
+impl Node {
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        let Some((node_str, child_nodes_str)) = line.split_once('=') else {
+            return Err(ParseError::at(line, line, "expected a node and its children separated by '='"));
+        };
+
+        let child_nodes_str = child_nodes_str.trim();
+        let Some((left_child_str, right_child_str)) = child_nodes_str
+            .strip_prefix('(')
+            .and_then(|x| x.strip_suffix(')'))
+            .and_then(|x| x.split_once(',')) else {
+            return Err(ParseError::at(line, child_nodes_str, "expected \"(<left>, <right>)\""));
+        };
+
+        Ok(Node {
+            id: node_str.trim().into(),
+            left_id: left_child_str.trim().into(),
+            right_id: right_child_str.trim().into(),
+        })
+    }
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let map: Map = input.parse()?;
 
-    let mut curr_node = &map.nodes["AAA"];
+    let mut curr_node = map.nodes.get("AAA").ok_or_else(|| ParseError::new("no node \"AAA\" found"))?;
     let mut instr_idx: usize = 0;
     let mut steps = 0;
 
@@ -75,11 +95,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         steps += 1;
     }
 
-    Some(steps)
+    Ok(steps)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let map: Map = input.parse().unwrap();
+pub fn part_two(input: &str) -> Result<u64, ParseError> {
+    let map: Map = input.parse()?;
 
     let curr_nodes: Vec<&Node> = map.nodes.iter().filter(|(k, _)| k.ends_with('A')).map(|(_, v)| v).collect();
 
@@ -101,9 +121,13 @@ pub fn part_two(input: &str) -> Option<u64> {
         steps
     }).collect();
 
-    let result = steps.iter().fold(1, |acc, &x| lcm(acc, x as u64));
+    // each ghost is assumed to get back to its start right after reaching its end, so it ends every `steps` steps.
+    let ends = steps.iter().map(|&x| Periodic::new(x as u64, x as u64));
+    let result = Periodic::intersect_all(ends)
+        .ok_or_else(|| ParseError::new("the ghosts never end at the same time"))?
+        .offset;
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -113,12 +137,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result.unwrap(), 6);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result.unwrap(), 6);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            LLR\n\
+            \n\
+            AAA = (BBB, BBB)\n\
+            BBB = (AAA, ZZZ)\n\
+            ZZZ = (ZZZ, ZZZ)");
+        advent_of_code::template::fuzz::check_parser(&input, Map::from_str);
     }
 }
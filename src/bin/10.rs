@@ -1,6 +1,7 @@
 use num::integer::div_ceil;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
+use advent_of_code::{ParseError, Polygon};
 advent_of_code::solution!(10);
 
 #[derive(Debug, Eq, PartialEq)]
@@ -10,22 +11,44 @@ enum Tile {
     Connection(Direction, Direction),
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '|' => Tile::Connection(Direction::North, Direction::South),
-            '-' => Tile::Connection(Direction::East, Direction::West),
-            'L' => Tile::Connection(Direction::North, Direction::East),
-            'J' => Tile::Connection(Direction::North, Direction::West),
-            '7' => Tile::Connection(Direction::South, Direction::West),
-            'F' => Tile::Connection(Direction::South, Direction::East),
-            '.' => Tile::Ground,
-            'S' => Tile::Start,
-            _ => panic!("invalid tile character")
+            '|' => Ok(Tile::Connection(Direction::North, Direction::South)),
+            '-' => Ok(Tile::Connection(Direction::East, Direction::West)),
+            'L' => Ok(Tile::Connection(Direction::North, Direction::East)),
+            'J' => Ok(Tile::Connection(Direction::North, Direction::West)),
+            '7' => Ok(Tile::Connection(Direction::South, Direction::West)),
+            'F' => Ok(Tile::Connection(Direction::South, Direction::East)),
+            '.' => Ok(Tile::Ground),
+            'S' => Ok(Tile::Start),
+            x => Err(ParseError::new(format!("invalid tile character {x:?}"))),
         }
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    let map: Vec<Vec<Tile>> = input.lines().map(|line| {
+        line.char_indices()
+            .map(|(idx, c)| Tile::try_from(c).map_err(|e| e.within(input, &line[idx..])))
+            .collect()
+    }).collect::<Result<_, _>>()?;
+
+    let width = map.first().map_or(0, |row| row.len());
+
+    if let Some((row, line)) = map.iter().zip(input.lines()).find(|(row, _)| row.len() != width) {
+        return Err(ParseError::at(input, line, format!("expected a row of width {width}, found {}", row.len())));
+    }
+
+    if find_start(&map).is_none() {
+        return Err(ParseError::new("no start tile found"));
+    }
+
+    Ok(map)
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone, EnumIter)]
 enum Direction {
     North,
@@ -117,10 +140,8 @@ fn find_conn(map: &[Vec<Tile>], conn: ((usize, usize), Direction)) -> Option<((u
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map: Vec<Vec<Tile>> = input.lines().map(|line| {
-        line.chars().map(Tile::from).collect()
-    }).collect();
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let map = parse(input)?;
 
     let start_pos = find_start(&map).expect("no start tile found");
 
@@ -134,13 +155,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(div_ceil(conns.len() as u32, 2))
+    Ok(div_ceil(conns.len() as u32, 2))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map: Vec<Vec<Tile>> = input.lines().map(|line| {
-        line.chars().map(Tile::from).collect()
-    }).collect();
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let map = parse(input)?;
 
     let start_pos = find_start(&map).expect("no start tile found");
 
@@ -157,25 +176,11 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     conns.push((start_pos, (conns[conns.len() - 1].1.1.inverse(), conns[0].1.0.inverse())));
 
-    let mut count = 0;
+    let pipe_loop = Polygon::new(conns.iter().map(|((row, col), _)| (*col as isize, *row as isize)));
 
-    for (row_idx, row) in map.iter().enumerate() {
-        let mut inside = false;
-
-        for (col_idx, _) in row.iter().enumerate() {
-            if let Some(conn) = conns.iter().find(|c| c.0 == (row_idx, col_idx)) {
-                match conn.1 {
-                    (Direction::South, _) => { inside = !inside }
-                    (_, Direction::South) => { inside = !inside }
-                    _ => {}
-                }
-            } else if inside {
-                count += 1;
-            }
-        }
-    }
+    let enclosed = pipe_loop.interior_points().ok_or_else(|| ParseError::new("the pipe loop crosses itself"))?;
 
-    Some(count)
+    Ok(enclosed as u32)
 }
 
 #[cfg(test)]
@@ -185,12 +190,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        assert_eq!(result.unwrap(), 4);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result.unwrap(), 8);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            .....\n\
+            .S-7.\n\
+            .|.|.\n\
+            .L-J.\n\
+            .....");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
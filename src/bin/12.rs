@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::iter;
-use itertools::{Itertools};
+use advent_of_code::{parse_at, ParseError};
 use rayon::iter::{*};
 advent_of_code::solution!(12);
 
@@ -11,17 +11,36 @@ enum State {
     Unknown,
 }
 
-impl From<char> for State {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for State {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => State::Working,
-            '#' => State::Damaged,
-            '?' => State::Unknown,
-            _ => panic!("invalid state")
+            '.' => Ok(State::Working),
+            '#' => Ok(State::Damaged),
+            '?' => Ok(State::Unknown),
+            x => Err(ParseError::new(format!("invalid state {x:?}"))),
         }
     }
 }
 
+type Record = (Vec<State>, Vec<usize>);
+
+fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+    input.lines().map(|line| {
+        let Some((first_part, second_part)) = line.split_once(' ') else {
+            return Err(ParseError::at(input, line, "expected springs and checksums separated by a space"));
+        };
+
+        let sequence: Vec<State> = first_part.char_indices()
+            .map(|(idx, c)| State::try_from(c).map_err(|e| e.within(input, &first_part[idx..])))
+            .collect::<Result<_, _>>()?;
+        let checksums: Vec<usize> = second_part.split(',').map(|c| parse_at(input, c)).collect::<Result<_, _>>()?;
+
+        Ok((sequence, checksums))
+    }).collect()
+}
+
 fn calc(sequence: &[State], seq_idx: usize, checksums: &[usize], checksums_idx: usize) -> usize {
     let mut cache = HashMap::new();
     calc_cache(sequence, seq_idx, checksums, checksums_idx, &mut cache)
@@ -66,40 +85,32 @@ fn calc_cache(sequence: &[State], seq_idx: usize, checksums: &[usize], checksums
     result
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let lines: Vec<(Vec<State>, Vec<usize>)> = input.lines().map(|line| {
-        let (first_part, second_part) = line.split(' ').tuples().next().unwrap();
-        let sequence: Vec<State> = first_part.chars().map(State::from).collect();
-        let checksums: Vec<usize> = second_part.split(',').map(|c| c.parse::<usize>().unwrap()).collect();
-        (sequence, checksums)
-    }).collect();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let lines = parse(input)?;
 
     let result: usize = lines.par_iter().map(|(seq, checksums)| calc(seq, 0, checksums, 0)).sum();
 
-    Some(result)
+    Ok(result)
 }
 
-fn unfold_sequence(states: &Vec<State>) -> Vec<State> {
+fn unfold_sequence(states: &[State]) -> Vec<State> {
     states.iter().chain(iter::once(&State::Unknown)).cloned().cycle().take((states.len() + 1) * 5 - 1).collect()
 }
 
-fn unfold_checksum(checksum: &Vec<usize>) -> Vec<usize> {
+fn unfold_checksum(checksum: &[usize]) -> Vec<usize> {
     checksum.iter().cloned().cycle().take(checksum.len() * 5).collect()
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let sequences = input.lines().map(|line| {
-        let (first_part, second_part) = line.split(' ').tuples().next().unwrap();
-        let init_seq: Vec<State> = first_part.chars().map(State::from).collect();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let sequences = parse(input)?.into_iter().map(|(init_seq, init_checksum)| {
         let exp_seq: Vec<State> = unfold_sequence(&init_seq);
-        let init_checksum: Vec<usize> = second_part.split(',').map(|c| c.parse::<usize>().unwrap()).collect();
         let exp_checksum: Vec<usize> = unfold_checksum(&init_checksum);
         (exp_seq, exp_checksum)
     });
 
     let result: usize = sequences.par_bridge().map(|(seq, checksums)| calc(&seq, 0, &checksums, 0)).sum();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -109,12 +120,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        assert_eq!(result.unwrap(), 21);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
+        assert_eq!(result.unwrap(), 525152);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            ???.### 1,1,3\n\
+            .??..??...?##. 1,1,3");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use advent_of_code::ParseError;
 advent_of_code::solution!(13);
 
 #[derive(Debug)]
@@ -29,7 +30,7 @@ impl Pattern {
     }
 
 
-    fn get_reflection_value_inner(grid: &Vec<Vec<char>>) -> Option<usize> {
+    fn get_reflection_value_inner(grid: &[Vec<char>]) -> Option<usize> {
         let duplicate_line_pairs: Vec<(usize, &[Vec<char>])> = grid.windows(2).enumerate().filter(|(_i, lines)| lines.iter().all_equal()).collect();
 
         for duplicate_line_pair in duplicate_line_pairs {
@@ -62,7 +63,7 @@ impl Pattern {
         None
     }
 
-    fn get_reflection_value_inner_2(grid: &Vec<Vec<char>>) -> Option<usize> {
+    fn get_reflection_value_inner_2(grid: &[Vec<char>]) -> Option<usize> {
         let duplicate_line_pairs: Vec<(usize, bool)> = grid.windows(2).enumerate().filter_map(|(i, lines)| {
             match Pattern::get_difference(lines) {
                 0 => Some((i, false)),
@@ -126,35 +127,54 @@ impl Pattern {
 }
 
 impl FromStr for Pattern {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let grid: Vec<Vec<char>> = s.lines().map(|line| {
             line.chars().collect()
         }).collect();
 
+        let width = grid.first().map_or(0, |row| row.len());
+
+        if width == 0 {
+            return Err(ParseError::at(s, s, "empty pattern"));
+        }
+
+        for line in s.lines() {
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| *c != '.' && *c != '#') {
+                return Err(ParseError::at(s, &line[idx..], format!("invalid tile {c:?}")));
+            }
+
+            if line.chars().count() != width {
+                return Err(ParseError::at(s, line, format!("expected a row of width {width}")));
+            }
+        }
+
         Ok(Pattern { grid })
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let blocks = input.split("\n\n");
+fn parse(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| Pattern::from_str(block).map_err(|e| e.within(input, block)))
+        .collect()
+}
 
-    let patterns = blocks.map(|block| Pattern::from_str(block).unwrap());
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let patterns = parse(input)?.into_iter();
 
     let result = patterns.map(|p| p.get_reflection_value()).sum();
 
-    Some(result)
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let blocks = input.split("\n\n");
-
-    let patterns = blocks.map(|block| Pattern::from_str(block).unwrap());
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let patterns = parse(input)?.into_iter();
 
     let result = patterns.map(|p| p.get_reflection_value_2()).sum();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -164,12 +184,28 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
+        assert_eq!(result.unwrap(), 405);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
+        assert_eq!(result.unwrap(), 400);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            #.##..##.\n\
+            ..#.##.#.\n\
+            ##......#\n\
+            ##......#\n\
+            ..#.##.#.\n\
+            \n\
+            #...##..#\n\
+            #....#..#\n\
+            ..##..###\n\
+            ..##..###");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
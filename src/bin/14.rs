@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use itertools::Itertools;
 use rayon::prelude::{*};
+use advent_of_code::{Cycle, ParseError};
 advent_of_code::solution!(14);
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
@@ -29,16 +29,39 @@ enum Direction {
     West,
 }
 
-impl From<char> for Item {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Item {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'O' => Item::Dish,
-            '#' => Item::Rock,
-            _ => Item::None,
+            'O' => Ok(Item::Dish),
+            '#' => Ok(Item::Rock),
+            '.' => Ok(Item::None),
+            x => Err(ParseError::new(format!("invalid item {x:?}"))),
         }
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Vec<Item>>, ParseError> {
+    let rows: Vec<Vec<Item>> = input.lines().map(|line| {
+        line.char_indices()
+            .map(|(idx, c)| Item::try_from(c).map_err(|e| e.within(input, &line[idx..])))
+            .collect()
+    }).collect::<Result<_, _>>()?;
+
+    let width = rows.first().map_or(0, |row| row.len());
+
+    if width == 0 {
+        return Err(ParseError::new("empty platform"));
+    }
+
+    if let Some((_, line)) = rows.iter().zip(input.lines()).find(|(row, _)| row.len() != width) {
+        return Err(ParseError::at(input, line, format!("expected a row of width {width}")));
+    }
+
+    Ok(rows)
+}
+
 fn calc(list: &mut HashMap<usize, &Item>, full_length: usize) -> usize {
     let mut result = 0;
 
@@ -72,12 +95,13 @@ fn calc(list: &mut HashMap<usize, &Item>, full_length: usize) -> usize {
     result
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let rows = parse(input)?;
+    let width = rows[0].len();
+    let height = rows.len();
 
-    let map: HashMap<(usize, usize), Item> = input.lines().enumerate().flat_map(|(row_idx, line)| {
-        line.chars().map(Item::from).enumerate().filter(|i| i.1 != Item::None).map(|(col_idx, i)| {
+    let map: HashMap<(usize, usize), Item> = rows.into_iter().enumerate().flat_map(|(row_idx, row)| {
+        row.into_iter().enumerate().filter(|i| i.1 != Item::None).map(|(col_idx, i)| {
             ((row_idx, col_idx), i)
         }).collect::<Vec<((usize, usize), Item)>>()
     }).collect();
@@ -89,7 +113,7 @@ pub fn part_one(input: &str) -> Option<usize> {
         calc(col, height)
     }).sum();
 
-    Some(result)
+    Ok(result)
 }
 
 fn mutate_list(list: &mut [Item], full_length: usize, dir: Direction) {
@@ -199,54 +223,23 @@ fn cycle(init_cols: Vec<Vec<Item>>, width: usize, height: usize) -> Vec<Vec<Item
     to_cols(list, width)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let rows = parse(input)?;
+    let width = rows[0].len();
+    let height = rows.len();
 
-    let map: Vec<(usize, usize, Item)> = input.lines().enumerate().flat_map(|(row_idx, line)| {
-        line.chars().map(Item::from).enumerate().map(|(col_idx, i)| {
+    let map: Vec<(usize, usize, Item)> = rows.into_iter().enumerate().flat_map(|(row_idx, row)| {
+        row.into_iter().enumerate().map(|(col_idx, i)| {
             (row_idx, col_idx, i)
         }).collect::<Vec<(usize, usize, Item)>>()
     }).collect();
 
     let init_cols: Vec<Vec<Item>> = (0..width).map(|y| map.iter().filter(|x| x.1 == y).map(|x| x.2).collect()).collect();
 
-    let mut curr_cols = init_cols;
-
-    let mut cache: Vec<Vec<Vec<Item>>> = Vec::new();
-
-    let target: usize = 1000000000;
-
-    let mut cycle_offset: usize = 0;
-
-    for i in 0..target {
-        curr_cols = cycle(curr_cols, width, height);
+    let spins = Cycle::hashed(init_cols, |cols| cycle(cols.clone(), width, height));
+    let result = spins.nth(1_000_000_000).iter().map(|result_col| calc_2(result_col)).sum();
 
-        // println!("Iteration: {}", i + 1);
-        // to_rows(curr_cols.clone(), height).iter().for_each(|row| {
-        //     row.iter().for_each(|i| print!("{}", i));
-        //     println!();
-        // });
-        // println!();
-
-        if let Some((cache_idx, _)) = cache.iter().find_position(|c| c == &&curr_cols) {
-            cycle_offset = (target - 1 - i) % (i - cache_idx);
-            println!("iteration {} is same as iteration {} (offset {})", cache_idx, i, &cycle_offset);
-            break;
-        }
-
-        cache.push(curr_cols.clone());
-    }
-
-    if cycle_offset != 0 {
-        for _ in 0..cycle_offset {
-            curr_cols = cycle(curr_cols, width, height);
-        }
-    }
-
-    let result = curr_cols.iter().map(|result_col| calc_2(result_col)).sum();
-
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -256,12 +249,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
+        assert_eq!(result.unwrap(), 136);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
+        assert_eq!(result.unwrap(), 64);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            O....#....\n\
+            O.OO#....#\n\
+            .....##...\n\
+            OO.#O....O");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
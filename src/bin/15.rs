@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(15);
 
 #[derive(Debug, Clone)]
@@ -15,21 +16,28 @@ enum Operation {
 }
 
 impl FromStr for Operation {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains('=') {
-            let (label, focal_length) = s.split('=').tuples().next().unwrap();
+        if let Some((label, focal_length)) = s.split_once('=') {
             let box_idx = calc_hash(label);
-            Ok(Operation::Add(box_idx, label.into(), focal_length.replace('\n', "").parse::<usize>().unwrap()))
-        } else if s.contains('-') {
-            let label = s.split('-').next().unwrap();
+            Ok(Operation::Add(box_idx, label.into(), parse_at(s, focal_length.trim_end_matches('\n'))?))
+        } else if let Some((label, _)) = s.split_once('-') {
             let box_idx = calc_hash(label);
             Ok(Operation::Remove(box_idx, label.into()))
-        } else { panic!("invalid step") }
+        } else {
+            Err(ParseError::at(s, s, "expected a step containing '=' or '-'"))
+        }
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Operation>, ParseError> {
+    input
+        .split(',')
+        .map(|step| Operation::from_str(step).map_err(|e| e.within(input, step)))
+        .collect()
+}
+
 fn calc_hash(input: &str) -> usize {
     input
         .chars()
@@ -43,9 +51,9 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
     let mut boxes: Vec<Vec<Lens>> = vec![vec!(); 256];
-    let operations: Vec<Operation> = input.split(',').map(|step| Operation::from_str(step).unwrap()).collect();
+    let operations = parse(input)?;
 
     for operation in operations {
         match operation {
@@ -72,7 +80,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             .sum::<usize>())
         .sum();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -88,6 +96,13 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(145));
+        assert_eq!(result.unwrap(), 145);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            rn=1,cm-,qp=3,cm=2");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
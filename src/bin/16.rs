@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use itertools::Itertools;
 use rayon::prelude::{*};
+use advent_of_code::{Grid, ParseError};
 advent_of_code::solution!(16);
 
 #[derive(Debug)]
@@ -44,15 +45,17 @@ impl Tile {
     }
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => Tile::Empty,
-            '/' => Tile::RMirror,
-            '\\' => Tile::LMirror,
-            '|' => Tile::VSplitter,
-            '-' => Tile::HSplitter,
-            _ => panic!("unknown tile")
+            '.' => Ok(Tile::Empty),
+            '/' => Ok(Tile::RMirror),
+            '\\' => Ok(Tile::LMirror),
+            '|' => Ok(Tile::VSplitter),
+            '-' => Ok(Tile::HSplitter),
+            x => Err(ParseError::new(format!("unknown tile {x:?}"))),
         }
     }
 }
@@ -73,24 +76,14 @@ struct Position {
 
 impl Position {
     fn move_direction(&self, direction: &Direction, map: &Map) -> Option<Position> {
-        match direction {
-            Direction::Up => {
-                if self.y == 0 { return None; }
-                Some(Position { x: self.x, y: self.y - 1 })
-            }
-            Direction::Down => {
-                if self.y == map.height - 1 { return None; }
-                Some(Position { x: self.x, y: self.y + 1 })
-            }
-            Direction::Left => {
-                if self.x == 0 { return None; }
-                Some(Position { x: self.x - 1, y: self.y })
-            }
-            Direction::Right => {
-                if self.x == map.width - 1 { return None; }
-                Some(Position { x: self.x + 1, y: self.y })
-            }
-        }
+        let delta = match direction {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        };
+
+        map.grid.offset((self.x, self.y), delta).map(|(x, y)| Position { x, y })
     }
 }
 
@@ -102,7 +95,7 @@ struct Ball {
 
 impl Ball {
     fn advance(&self, map: &Map, history: &[(Position, Direction)]) -> Vec<Ball> {
-        map.grid[self.position.y][self.position.x]
+        map.grid[(self.position.x, self.position.y)]
             .get_new_directions(&self.direction)
             .iter()
             .flat_map(|new_direction| {
@@ -123,17 +116,14 @@ impl Ball {
 
 #[derive(Debug)]
 struct Map {
-    grid: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
+    grid: Grid<Tile>,
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s.lines().map(|line| line.chars().map(Tile::from).collect()).collect();
-        Ok(Map { grid, width: s.lines().next().unwrap().chars().count(), height: s.lines().count() })
+        Ok(Map { grid: Grid::try_parse(s)? })
     }
 }
 
@@ -158,8 +148,8 @@ fn calc(ball: &Ball, map: &Map) -> usize {
     history.iter().map(|h| h.0).unique().count()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = Map::from_str(input).unwrap();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let map = Map::from_str(input)?;
 
     let ball = Ball {
         position: Position { x: 0, y: 0 },
@@ -168,21 +158,22 @@ pub fn part_one(input: &str) -> Option<usize> {
 
     let result = calc(&ball, &map);
 
-    Some(result)
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = Map::from_str(input).unwrap();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let map = Map::from_str(input)?;
+    let (width, height) = (map.grid.width(), map.grid.height());
 
-    let balls: Vec<Ball> = (0..map.width).map(|x| Ball { position: Position { x, y: 0 }, direction: Direction::Down })
-        .chain((0..map.width).map(|x| Ball { position: Position { x, y: map.height - 1 }, direction: Direction::Up }))
-        .chain((0..map.height).map(|y| Ball { position: Position { x: 0, y }, direction: Direction::Right }))
-        .chain((0..map.height).map(|y| Ball { position: Position { x: map.width - 1, y }, direction: Direction::Left }))
+    let balls: Vec<Ball> = (0..width).map(|x| Ball { position: Position { x, y: 0 }, direction: Direction::Down })
+        .chain((0..width).map(|x| Ball { position: Position { x, y: height - 1 }, direction: Direction::Up }))
+        .chain((0..height).map(|y| Ball { position: Position { x: 0, y }, direction: Direction::Right }))
+        .chain((0..height).map(|y| Ball { position: Position { x: width - 1, y }, direction: Direction::Left }))
         .collect();
 
     let result = balls.par_iter().map(|ball| calc(ball, &map)).max().unwrap();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -192,12 +183,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result.unwrap(), 46);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(51));
+        assert_eq!(result.unwrap(), 51);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            .|...\\....\n\
+            |.-.\\.....\n\
+            .....|-...\n\
+            ........|.");
+        advent_of_code::template::fuzz::check_parser(&input, Map::from_str);
     }
 }
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use pathfinding::matrix::{directions, Matrix};
 use pathfinding::prelude::astar;
+use advent_of_code::ParseError;
 advent_of_code::solution!(17);
 
 #[derive(Debug)]
@@ -16,10 +17,20 @@ struct State {
 }
 
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let grid = s.lines().map(|line| line.chars().map(|c| c.to_digit(10).unwrap() as usize)).collect();
+        let rows: Vec<Vec<usize>> = s.lines().map(|line| {
+            line.char_indices().map(|(idx, c)| {
+                c.to_digit(10).map(|d| d as usize).ok_or_else(|| ParseError::at(s, &line[idx..], format!("expected a digit, found {c:?}")))
+            }).collect()
+        }).collect::<Result<_, _>>()?;
+
+        if rows.first().is_none_or(|row| row.is_empty()) {
+            return Err(ParseError::new("empty map"));
+        }
+
+        let grid = Matrix::from_rows(rows).map_err(|e| ParseError::new("rows differ in width").with_source(e))?;
         Ok(Map { grid })
     }
 }
@@ -80,20 +91,20 @@ fn calc<const MIN_DIST: usize, const MAX_DIST: usize>(map: Map, start: State, fi
     path.1
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = Map::from_str(input).unwrap();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let map = Map::from_str(input)?;
     let start = State { position: (0, 0), direction: (0, 0), distance: 0 };
     let finish_pos = (map.grid.rows - 1, map.grid.columns - 1);
     let result = calc::<1, 3>(map, start, finish_pos);
-    Some(result)
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = Map::from_str(input).unwrap();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let map = Map::from_str(input)?;
     let start = State { position: (0, 0), direction: (0, 0), distance: 0 };
     let finish_pos = (map.grid.rows - 1, map.grid.columns - 1);
     let result = calc::<4, 10>(map, start, finish_pos);
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -103,12 +114,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(102));
+        assert_eq!(result.unwrap(), 102);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
+        assert_eq!(result.unwrap(), 94);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            2413\n\
+            3215\n\
+            3255");
+        advent_of_code::template::fuzz::check_parser(&input, Map::from_str);
     }
 }
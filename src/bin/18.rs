@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use advent_of_code::{parse_at, Direction, ParseError, Polygon};
 advent_of_code::solution!(18);
 
 #[derive(Debug)]
@@ -9,13 +10,18 @@ struct Instruction {
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (direction_str, amount_str) = s.split(' ').tuples().next().unwrap();
+        let Some((direction_str, amount_str)) = s.split_whitespace().tuples().next() else {
+            return Err(ParseError::at(s, s, "expected a direction and an amount"));
+        };
+
+        let direction = direction_str.chars().next().unwrap_or_default();
+
         Ok(Instruction {
-            direction: Direction::from(direction_str.chars().next().unwrap()),
-            amount: amount_str.parse().unwrap(),
+            direction: Direction::try_from(direction).map_err(|e| e.within(s, direction_str))?,
+            amount: parse_at(s, amount_str)?,
         })
     }
 }
@@ -26,93 +32,67 @@ struct ExtendedInstruction {
     amount: usize,
 }
 
-impl ExtendedInstruction {
-    fn to_instruction(&self) -> Instruction {
-        Instruction { direction: self.direction, amount: self.amount }
+impl From<ExtendedInstruction> for Instruction {
+    fn from(value: ExtendedInstruction) -> Self {
+        Instruction { direction: value.direction, amount: value.amount }
     }
 }
 
 impl FromStr for ExtendedInstruction {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let hex_str = s.split(' ').last().unwrap();
-        let raw_hex_str = hex_str.replace(['(', ')'], "");
-        let amount = usize::from_str_radix(&raw_hex_str[1..raw_hex_str.len() - 1], 16).expect("invalid hex code");
-        let direction = Direction::from(raw_hex_str.chars().last().unwrap());
+        let hex_str = s.split(' ').next_back().unwrap_or_default();
+
+        let Some(raw_hex_str) = hex_str
+            .strip_prefix("(#")
+            .and_then(|x| x.strip_suffix(')'))
+            .filter(|x| x.len() == 6 && x.chars().all(|c| c.is_ascii_hexdigit())) else {
+            return Err(ParseError::at(s, hex_str, "expected a color code like \"(#70c710)\""));
+        };
+
+        let amount = usize::from_str_radix(&raw_hex_str[..5], 16).map_err(|e| ParseError::at(s, raw_hex_str, "invalid hex code").with_source(e))?;
+        let direction = match &raw_hex_str[5..] {
+            "0" => Direction::Right,
+            "1" => Direction::Down,
+            "2" => Direction::Left,
+            "3" => Direction::Up,
+            x => return Err(ParseError::at(s, x, format!("invalid direction {x:?}"))),
+        };
         Ok(ExtendedInstruction { direction, amount })
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            'U' | '3' => Direction::North,
-            'D' | '1' => Direction::South,
-            'L' | '2' => Direction::West,
-            'R' | '0' => Direction::East,
-            _ => panic!("invalid direction")
-        }
-    }
-}
-
-#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
-struct Position {
-    x: isize,
-    y: isize,
-}
-
-impl Position {
-    fn advance_amount(&self, direction: Direction, amount: usize) -> Position {
-        match direction {
-            Direction::North => Position { x: self.x, y: self.y - amount as isize },
-            Direction::South => Position { x: self.x, y: self.y + amount as isize },
-            Direction::West => Position { x: self.x - amount as isize, y: self.y },
-            Direction::East => Position { x: self.x + amount as isize, y: self.y },
-        }
+fn parse<T>(input: &str) -> Result<Vec<Instruction>, ParseError>
+where
+    T: FromStr<Err = ParseError> + Into<Instruction>,
+{
+    let instructions: Vec<Instruction> = input
+        .lines()
+        .map(|line| T::from_str(line).map(Into::into).map_err(|e| e.within(input, line)))
+        .collect::<Result<_, _>>()?;
+
+    if instructions.is_empty() {
+        return Err(ParseError::new("no instructions found"));
     }
-}
-
-fn build_corners(instructions: &[Instruction]) -> Vec<Position> {
-    let mut curr_pos = Position { x: 0, y: 0 };
 
-    instructions.iter().map(|instruction| {
-        let new_pos = curr_pos.advance_amount(instruction.direction, instruction.amount);
-        curr_pos = new_pos;
-        new_pos
-    }).collect()
+    Ok(instructions)
 }
 
-fn count_inside_shoelace(corners: &[Position]) -> isize {
-    let mut mults: Vec<(isize, isize)> = corners.iter().tuple_windows().map(|(curr, next)| (curr.x * next.y, curr.y * next.x)).collect();
-    mults.push((corners[corners.len() - 1].x * corners[0].y, corners[corners.len() - 1].y * corners[0].x));
-    let (mults_a, mults_b): (Vec<isize>, Vec<isize>) = mults.iter().cloned().unzip();
-    let result = (mults_a.iter().sum::<isize>() - mults_b.iter().sum::<isize>()) / 2;
-    result
+fn count_dug(instructions: &[Instruction]) -> Result<isize, ParseError> {
+    let steps = instructions.iter().map(|instruction| (instruction.direction, instruction.amount as isize));
+    let dug = Polygon::from_walk((0, 0), steps).lattice_points().ok_or_else(|| ParseError::new("the dig plan crosses itself"))?;
+    Ok(dug as isize)
 }
 
-pub fn part_one(input: &str) -> Option<isize> {
-    let instructions: Vec<Instruction> = input.lines().map(|line| Instruction::from_str(line).unwrap()).collect();
-    let corners = build_corners(&instructions);
-    let inside_area = count_inside_shoelace(&corners);
-    let border_count = instructions.iter().map(|x| x.amount).sum::<usize>();
-    Some(inside_area + (border_count / 2) as isize + 1)
+pub fn part_one(input: &str) -> Result<isize, ParseError> {
+    let instructions = parse::<Instruction>(input)?;
+    count_dug(&instructions)
 }
 
-pub fn part_two(input: &str) -> Option<isize> {
-    let instructions: Vec<Instruction> = input.lines().map(|line| ExtendedInstruction::from_str(line).unwrap().to_instruction()).collect();
-    let corners = build_corners(&instructions);
-    let inside_area = count_inside_shoelace(&corners);
-    let border_count = instructions.iter().map(|x| x.amount).sum::<usize>();
-    Some(inside_area + (border_count / 2) as isize + 1)
+pub fn part_two(input: &str) -> Result<isize, ParseError> {
+    let instructions = parse::<ExtendedInstruction>(input)?;
+    count_dug(&instructions)
 }
 
 #[cfg(test)]
@@ -122,12 +102,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(62));
+        assert_eq!(result.unwrap(), 62);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(952408144115));
+        assert_eq!(result.unwrap(), 952408144115);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            R 6 (#70c710)\n\
+            D 5 (#0dc571)\n\
+            L 6 (#5713f0)\n\
+            U 5 (#caa173)");
+        advent_of_code::template::fuzz::check_parser(&input, parse::<Instruction>);
+        advent_of_code::template::fuzz::check_parser(&input, parse::<ExtendedInstruction>);
     }
 }
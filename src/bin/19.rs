@@ -1,9 +1,8 @@
-use std::cmp::{max, min, Ordering};
+use std::cmp::Ordering;
 use std::cmp::Ordering::{Equal, Greater, Less};
-use std::collections::HashMap;
-use std::ops::Range;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use itertools::Itertools;
+use advent_of_code::{parse_at, Interval, IntervalBox, ParseError};
 advent_of_code::solution!(19);
 
 #[derive(Debug)]
@@ -14,32 +13,52 @@ struct Part {
     s: usize,
 }
 
-#[derive(Debug)]
-struct HypPart {
-    x: Range<usize>,
-    m: Range<usize>,
-    a: Range<usize>,
-    s: Range<usize>,
-}
+/// All parts with ratings in a range per category, in the order x, m, a, s.
+type HypPart = IntervalBox<usize, 4>;
 
 impl FromStr for Part {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let categories: HashMap<String, usize> = s[1..s.len() - 1].split(',').map(|c| {
-            let (c_name, c_value) = c.split('=').tuples().next().unwrap();
-            (c_name.to_string(), c_value.parse::<usize>().unwrap())
-        }).collect();
+        let Some(categories_str) = s.strip_prefix('{').and_then(|x| x.strip_suffix('}')) else {
+            return Err(ParseError::at(s, s, "expected a part like \"{x=787,m=2655,a=1222,s=2876}\""));
+        };
+
+        let categories: HashMap<&str, usize> = categories_str.split(',').map(|c| {
+            let Some((c_name, c_value)) = c.split_once('=') else {
+                return Err(ParseError::at(s, c, "expected a category and a value separated by '='"));
+            };
+            Ok((parse_category(s, c_name)?, parse_at(s, c_value)?))
+        }).collect::<Result<_, _>>()?;
+
+        let get = |name: &str| categories.get(name).copied().ok_or_else(|| ParseError::at(s, s, format!("missing category {name:?}")));
 
         Ok(Part {
-            x: categories["x"],
-            m: categories["m"],
-            a: categories["a"],
-            s: categories["s"],
+            x: get("x")?,
+            m: get("m")?,
+            a: get("a")?,
+            s: get("s")?,
         })
     }
 }
 
+fn parse_category<'a>(line: &str, s: &'a str) -> Result<&'a str, ParseError> {
+    match s {
+        "x" | "m" | "a" | "s" => Ok(s),
+        _ => Err(ParseError::at(line, s, format!("invalid category {s:?}"))),
+    }
+}
+
+fn category_axis(category: &str) -> usize {
+    match category {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => panic!("invalid category"),
+    }
+}
+
 impl Part {
     fn get_category_value(&self, category: &str) -> usize {
         match category {
@@ -60,45 +79,75 @@ struct Workflow {
 }
 
 impl FromStr for Workflow {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (name_part, transitions_part) = s.split('{').tuples().next().unwrap();
-        let transitions_str = transitions_part[0..transitions_part.len() - 1].split(',');
+        let Some((name_part, transitions_part)) = s.split_once('{').and_then(|(name, rest)| Some((name, rest.strip_suffix('}')?))) else {
+            return Err(ParseError::at(s, s, "expected a workflow like \"px{a<2006:qkq,rfg}\""));
+        };
 
         let mut transitions: Vec<Transition> = vec!();
 
-        transitions_str.for_each(|tr| {
-            let parts: Vec<&str> = tr.split(':').collect();
-
-            if parts.len() == 1 {
+        for tr in transitions_part.split(',') {
+            let Some((condition_str, workflow)) = tr.split_once(':') else {
                 let condition = Box::new(|_: &Part| -> bool { true });
-                transitions.push((condition, ("x".into(), Equal, 0), parts.first().unwrap().to_string()));
-            } else {
-                let (condition_str, workflow) = parts.iter().tuples().next().unwrap();
-
-                return if condition_str.contains('>') {
-                    let (category_str, number_str) = condition_str.split('>').tuples().next().unwrap();
-                    let category = category_str.to_owned();
-                    let category_2 = category.clone();
-                    let number = number_str.parse::<usize>().unwrap();
-                    let condition = Box::new(move |part: &Part| -> bool { part.get_category_value(&category) > number });
-                    transitions.push((condition, (category_2, Greater, number), workflow.to_string()));
-                } else {
-                    let (category_str, number_str) = condition_str.split('<').tuples().next().unwrap();
-                    let category = category_str.to_owned();
-                    let category_2 = category.clone();
-                    let number = number_str.parse::<usize>().unwrap();
-                    let condition = Box::new(move |part: &Part| -> bool { part.get_category_value(&category) < number });
-                    transitions.push((condition, (category_2, Less, number), workflow.to_string()));
-                };
-            }
-        });
+                transitions.push((condition, ("x".into(), Equal, 0), tr.to_string()));
+                continue;
+            };
+
+            let Some((category_str, number_str, ordering)) = condition_str.split_once('>').map(|(c, n)| (c, n, Greater))
+                .or_else(|| condition_str.split_once('<').map(|(c, n)| (c, n, Less))) else {
+                return Err(ParseError::at(s, condition_str, "expected a condition using '<' or '>'"));
+            };
+
+            let category = parse_category(s, category_str)?.to_owned();
+            let category_2 = category.clone();
+            let number = parse_at::<usize>(s, number_str)?;
+
+            let condition: Box<dyn Fn(&Part) -> bool + Send + Sync> = match ordering {
+                Greater => Box::new(move |part: &Part| -> bool { part.get_category_value(&category) > number }),
+                _ => Box::new(move |part: &Part| -> bool { part.get_category_value(&category) < number }),
+            };
+
+            transitions.push((condition, (category_2, ordering, number), workflow.to_string()));
+        }
+
+        if transitions.last().is_none_or(|(_, (_, ordering, _), _)| *ordering != Equal) {
+            return Err(ParseError::at(s, transitions_part, "expected the last rule to be a fallback"));
+        }
 
         Ok(Workflow { name: name_part.into(), transitions })
     }
 }
 
+fn parse(input: &str) -> Result<(HashMap<String, Workflow>, Vec<Part>), ParseError> {
+    let Some((workflows_block, parts_block)) = input.split_once("\n\n") else {
+        return Err(ParseError::new("expected workflows and parts separated by an empty line"));
+    };
+
+    let workflows: Vec<(&str, Workflow)> = workflows_block.lines().map(|w| {
+        Ok((w, Workflow::from_str(w).map_err(|e| e.within(input, w))?))
+    }).collect::<Result<_, ParseError>>()?;
+
+    let names: HashSet<&str> = workflows.iter().map(|(_, workflow)| workflow.name.as_str()).collect();
+
+    if !names.contains("in") {
+        return Err(ParseError::new("no workflow \"in\" found"));
+    }
+
+    for (w, workflow) in &workflows {
+        if let Some((_, _, target)) = workflow.transitions.iter().find(|(_, _, target)| !matches!(target.as_str(), "A" | "R") && !names.contains(target.as_str())) {
+            return Err(ParseError::at(input, w, format!("unknown workflow {target:?}")));
+        }
+    }
+
+    let workflows: HashMap<String, Workflow> = workflows.into_iter().map(|(_, workflow)| (workflow.name.clone(), workflow)).collect();
+
+    let parts = parts_block.lines().map(|p| Part::from_str(p).map_err(|e| e.within(input, p))).collect::<Result<_, _>>()?;
+
+    Ok((workflows, parts))
+}
+
 impl Workflow {
     fn send_part(&self, part: &Part) -> String {
         for (condition, _, workflow) in &self.transitions {
@@ -112,171 +161,23 @@ impl Workflow {
 
     fn send_hyp_part(&self, hyp_part: &HypPart) -> Vec<(String, HypPart)> {
         let mut result = vec!();
-
-        let mut curr_hyp_part: HypPart = HypPart {
-            x: Range { start: hyp_part.x.start, end: hyp_part.x.end },
-            m: Range { start: hyp_part.m.start, end: hyp_part.m.end },
-            a: Range { start: hyp_part.a.start, end: hyp_part.a.end },
-            s: Range { start: hyp_part.s.start, end: hyp_part.s.end },
-        };
+        let mut curr_hyp_part = Some(*hyp_part);
 
         for (_, (category, ordering, number), workflow) in &self.transitions {
-            match ordering {
-                Less => {
-                    match &category[..] {
-                        "x" => {
-                            if curr_hyp_part.x.start < *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: Range { start: curr_hyp_part.x.start, end: number - 1 },
-                                    m: curr_hyp_part.m.clone(),
-                                    a: curr_hyp_part.a.clone(),
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: Range { start: *number, end: curr_hyp_part.x.end },
-                                m: curr_hyp_part.m.clone(),
-                                a: curr_hyp_part.a.clone(),
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "m" => {
-                            if curr_hyp_part.m.start < *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: Range { start: curr_hyp_part.m.start, end: number - 1 },
-                                    a: curr_hyp_part.a.clone(),
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: Range { start: *number, end: curr_hyp_part.m.end },
-                                a: curr_hyp_part.a.clone(),
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "a" => {
-                            if curr_hyp_part.a.start < *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: curr_hyp_part.m.clone(),
-                                    a: Range { start: curr_hyp_part.a.start, end: number - 1 },
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: curr_hyp_part.m.clone(),
-                                a: Range { start: *number, end: curr_hyp_part.a.end },
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "s" => {
-                            if curr_hyp_part.s.start < *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: curr_hyp_part.m.clone(),
-                                    a: curr_hyp_part.a.clone(),
-                                    s: Range { start: curr_hyp_part.s.start, end: number - 1 },
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: curr_hyp_part.m.clone(),
-                                a: curr_hyp_part.a.clone(),
-                                s: Range { start: *number, end: curr_hyp_part.s.end },
-                            }
-                        }
-                        _ => panic!("invalid category"),
-                    }
-                }
+            let Some(hyp_part) = curr_hyp_part else { break };
+
+            let axis = category_axis(category);
+            let (sent, rest) = match ordering {
                 Greater => {
-                    match &category[..] {
-                        "x" => {
-                            if curr_hyp_part.x.end > *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: Range { start: number + 1, end: curr_hyp_part.x.end },
-                                    m: curr_hyp_part.m.clone(),
-                                    a: curr_hyp_part.a.clone(),
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: Range { start: curr_hyp_part.x.start, end: *number },
-                                m: curr_hyp_part.m.clone(),
-                                a: curr_hyp_part.a.clone(),
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "m" => {
-                            if curr_hyp_part.m.end > *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: Range { start: number + 1, end: curr_hyp_part.m.end },
-                                    a: curr_hyp_part.a.clone(),
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: Range { start: curr_hyp_part.m.start, end: *number },
-                                a: curr_hyp_part.a.clone(),
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "a" => {
-                            if curr_hyp_part.a.end > *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: curr_hyp_part.m.clone(),
-                                    a: Range { start: number + 1, end: curr_hyp_part.a.end },
-                                    s: curr_hyp_part.s.clone(),
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: curr_hyp_part.m.clone(),
-                                a: Range { start: curr_hyp_part.a.start, end: *number },
-                                s: curr_hyp_part.s.clone(),
-                            }
-                        }
-                        "s" => {
-                            if curr_hyp_part.s.end > *number {
-                                result.push((workflow.clone(), HypPart {
-                                    x: curr_hyp_part.x.clone(),
-                                    m: curr_hyp_part.m.clone(),
-                                    a: curr_hyp_part.a.clone(),
-                                    s: Range { start: number + 1, end: curr_hyp_part.s.end },
-                                }));
-                            }
-
-                            curr_hyp_part = HypPart {
-                                x: curr_hyp_part.x.clone(),
-                                m: curr_hyp_part.m.clone(),
-                                a: curr_hyp_part.a.clone(),
-                                s: Range { start: curr_hyp_part.s.start, end: *number },
-                            }
-                        }
-                        _ => panic!("invalid category"),
-                    }
+                    let (below, above) = hyp_part.split_at(axis, number + 1);
+                    (above, below)
                 }
-                Equal => {
-                    result.push((workflow.clone(), HypPart {
-                        x: curr_hyp_part.x.clone(),
-                        m: curr_hyp_part.m.clone(),
-                        a: curr_hyp_part.a.clone(),
-                        s: curr_hyp_part.s.clone(),
-                    }));
-                }
-            }
+                Less => hyp_part.split_at(axis, *number),
+                Equal => (Some(hyp_part), None),
+            };
+
+            result.extend(sent.map(|sent| (workflow.clone(), sent)));
+            curr_hyp_part = rest;
         }
 
         result
@@ -293,27 +194,16 @@ fn calc(part: &Part, workflows: &HashMap<String, Workflow>) -> bool {
     curr_workflow_name == "A"
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let (workflows_block, parts_block) = input.split("\n\n").tuples().next().unwrap();
-
-    let parts = parts_block.lines().map(|p| Part::from_str(p).unwrap());
-    let workflows: HashMap<String, Workflow> = workflows_block.lines().map(|w| {
-        let workflow = Workflow::from_str(w).unwrap();
-        (workflow.name.clone(), workflow)
-    }).collect();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let (workflows, parts) = parse(input)?;
 
-    let result = parts.filter(|part| calc(part, &workflows)).map(|part| part.x + part.m + part.a + part.s).sum();
+    let result = parts.iter().filter(|part| calc(part, &workflows)).map(|part| part.x + part.m + part.a + part.s).sum();
 
-    Some(result)
+    Ok(result)
 }
 
 fn calc_2(workflows: &HashMap<String, Workflow>) -> Vec<HypPart> {
-    let hyp_part = HypPart {
-        x: Range { start: 1, end: 4000 },
-        m: Range { start: 1, end: 4000 },
-        a: Range { start: 1, end: 4000 },
-        s: Range { start: 1, end: 4000 },
-    };
+    let hyp_part = HypPart::new([Interval::inclusive(1, 4000); 4]);
 
     let mut accepted: Vec<HypPart> = vec!();
 
@@ -337,61 +227,11 @@ fn calc_2(workflows: &HashMap<String, Workflow>) -> Vec<HypPart> {
     accepted
 }
 
-fn get_overlap(me: &HypPart, other: &HypPart) -> usize {
-    let max_start_x = max(me.x.start, other.x.start);
-    let min_end_x = min(me.x.end, other.x.end);
-    let overlap_x = if min_end_x >= max_start_x { min_end_x - max_start_x + 1 } else { 0 };
-
-    let max_start_m = max(me.m.start, other.m.start);
-    let min_end_m = min(me.m.end, other.m.end);
-    let overlap_m = if min_end_m >= max_start_m { min_end_m - max_start_m + 1 } else { 0 };
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let (workflows, _) = parse(input)?;
 
-    let max_start_a = max(me.a.start, other.a.start);
-    let min_end_a = min(me.a.end, other.a.end);
-    let overlap_a = if min_end_a >= max_start_a { min_end_a - max_start_a + 1 } else { 0 };
-
-    let max_start_s = max(me.s.start, other.s.start);
-    let min_end_s = min(me.s.end, other.s.end);
-    let overlap_s = if min_end_s >= max_start_s { min_end_s - max_start_s + 1 } else { 0 };
-
-    overlap_x * overlap_m * overlap_a * overlap_s
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let (workflows_block, _) = input.split("\n\n").tuples().next().unwrap();
-
-    let workflows: HashMap<String, Workflow> = workflows_block.lines().map(|w| {
-        let workflow = Workflow::from_str(w).unwrap();
-        (workflow.name.clone(), workflow)
-    }).collect();
-
-    let hyp_results = calc_2(&workflows);
-
-    let mut visited_hyp_results: Vec<&HypPart> = vec!();
-
-    let mut combinations = 0;
-
-    for hyp_result in &hyp_results {
-        let x_comb = hyp_result.x.end - hyp_result.x.start + 1;
-        let m_comb = hyp_result.m.end - hyp_result.m.start + 1;
-        let a_comb = hyp_result.a.end - hyp_result.a.start + 1;
-        let s_comb = hyp_result.s.end - hyp_result.s.start + 1;
-        let comb = x_comb * m_comb * a_comb * s_comb;
-
-        let mut deduct = 0;
-
-        for visited in &visited_hyp_results {
-            let overlap = get_overlap(hyp_result, visited);
-            deduct += overlap;
-        }
-
-        deduct = min(deduct, comb);
-
-        combinations += comb - deduct;
-        visited_hyp_results.push(hyp_result);
-    }
-
-    Some(combinations)
+    // every part takes a single path through the workflows, so the accepted boxes are disjoint.
+    Ok(calc_2(&workflows).iter().map(HypPart::volume).sum())
 }
 
 #[cfg(test)]
@@ -401,12 +241,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(19114));
+        assert_eq!(result.unwrap(), 19114);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(167409079868000));
+        assert_eq!(result.unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            in{s<1351:px,A}\n\
+            px{a<2006:R,A}\n\
+            \n\
+            {x=787,m=2655,a=1222,s=2876}");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::str::Lines;
-use itertools::Itertools;
+use advent_of_code::{ParseError, Periodic};
 advent_of_code::solution!(20);
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -117,30 +116,62 @@ impl Module for ConjunctionModule {
     }
 }
 
-fn create_module(line: &str, all_lines: &Lines) -> Box<dyn Module> {
-    let (id_part, output_part) = line.split(" -> ").tuples().next().unwrap();
-
-    let output_ids = output_part.split(", ");
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum ModuleKind {
+    Broadcast,
+    FlipFlop,
+    Conjunction,
+}
 
-    if id_part.starts_with('%') {
-        return Box::new(FlipFlopModule::new(id_part[1..id_part.len()].into(), output_ids.map(|o| o.to_string()).collect()));
+fn parse_definition(line: &str) -> Result<(ModuleKind, &str, Vec<&str>), ParseError> {
+    let Some((id_part, output_part)) = line.split_once(" -> ") else {
+        return Err(ParseError::at(line, line, "expected a module and its outputs separated by \" -> \""));
+    };
+
+    let (kind, id) = if let Some(id) = id_part.strip_prefix('%') {
+        (ModuleKind::FlipFlop, id)
+    } else if let Some(id) = id_part.strip_prefix('&') {
+        (ModuleKind::Conjunction, id)
+    } else {
+        (ModuleKind::Broadcast, id_part)
+    };
+
+    if id.is_empty() {
+        return Err(ParseError::at(line, id_part, "expected a module id"));
     }
 
-    if id_part.starts_with('&') {
-        let other_ids = all_lines.clone().filter_map(|l| {
-            let (other_id_part, other_output_part) = l.split(" -> ").tuples().next().unwrap();
+    Ok((kind, id, output_part.split(", ").collect()))
+}
 
-            if other_output_part.contains(&id_part[1..id_part.len()]) {
-                Some(other_id_part[1..other_id_part.len()].to_string())
-            } else {
-                None
+fn parse(input: &str) -> Result<HashMap<String, Box<dyn Module>>, ParseError> {
+    let definitions: Vec<(ModuleKind, &str, Vec<&str>)> = input
+        .lines()
+        .map(|line| parse_definition(line).map_err(|e| e.within(input, line)))
+        .collect::<Result<_, _>>()?;
+
+    let modules: HashMap<String, Box<dyn Module>> = definitions.iter().map(|(kind, id, outputs)| {
+        let output_ids = outputs.iter().map(|o| o.to_string()).collect();
+
+        let module: Box<dyn Module> = match kind {
+            ModuleKind::FlipFlop => Box::new(FlipFlopModule::new(id.to_string(), output_ids)),
+            ModuleKind::Conjunction => {
+                let other_ids = definitions.iter()
+                    .filter(|(_, _, other_outputs)| other_outputs.contains(id))
+                    .map(|(_, other_id, _)| other_id.to_string());
+
+                Box::new(ConjunctionModule::new(id.to_string(), other_ids.collect(), output_ids))
             }
-        });
+            ModuleKind::Broadcast => Box::new(BroadcastModule::new(id.to_string(), output_ids)),
+        };
 
-        return Box::new(ConjunctionModule::new(id_part[1..id_part.len()].into(), other_ids.collect(), output_ids.map(|o| o.to_string()).collect()));
+        (id.to_string(), module)
+    }).collect();
+
+    if !modules.contains_key("broadcaster") {
+        return Err(ParseError::new("no module \"broadcaster\" found"));
     }
 
-    Box::new(BroadcastModule::new(id_part.into(), output_ids.map(|o| o.to_string()).collect()))
+    Ok(modules)
 }
 
 fn run(modules: &mut HashMap<String, Box<dyn Module>>) -> (usize, usize) {
@@ -170,19 +201,14 @@ fn run(modules: &mut HashMap<String, Box<dyn Module>>) -> (usize, usize) {
     (low_counter + 1, high_counter)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let all_lines = input.lines();
-
-    let mut modules: HashMap<_, _> = input.lines().map(|l| {
-        let module = create_module(l, &all_lines);
-        (module.id().clone(), module)
-    }).collect();
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let mut modules = parse(input)?;
 
     let result = (0..1000)
         .map(|_| run(&mut modules))
         .fold((0, 0), |acc, curr| (acc.0 + curr.0, acc.1 + curr.1));
 
-    Some(result.0 * result.1)
+    Ok(result.0 * result.1)
 }
 
 fn run_2(modules: &mut HashMap<String, Box<dyn Module>>, check_id: &str) -> bool {
@@ -209,15 +235,13 @@ fn run_2(modules: &mut HashMap<String, Box<dyn Module>>, check_id: &str) -> bool
     false
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let all_lines = input.lines();
-
-    let mut modules: HashMap<_, _> = input.lines().map(|l| {
-        let module = create_module(l, &all_lines);
-        (module.id().clone(), module)
-    }).collect();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let mut modules = parse(input)?;
 
     // dh, qd, bb, dp,
+    if let Some(id) = ["dh", "qd", "bb", "dp"].iter().find(|id| !modules.contains_key(**id)) {
+        return Err(ParseError::new(format!("no module {id:?} found")));
+    }
 
     let mut dh_presses = 1;
 
@@ -225,10 +249,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         dh_presses += 1
     }
 
-    let mut modules: HashMap<_, _> = input.lines().map(|l| {
-        let module = create_module(l, &all_lines);
-        (module.id().clone(), module)
-    }).collect();
+    let mut modules = parse(input)?;
 
     let mut qd_presses = 1;
 
@@ -236,10 +257,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         qd_presses += 1
     }
 
-    let mut modules: HashMap<_, _> = input.lines().map(|l| {
-        let module = create_module(l, &all_lines);
-        (module.id().clone(), module)
-    }).collect();
+    let mut modules = parse(input)?;
 
     let mut bb_presses = 1;
 
@@ -247,10 +265,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         bb_presses += 1
     }
 
-    let mut modules: HashMap<_, _> = input.lines().map(|l| {
-        let module = create_module(l, &all_lines);
-        (module.id().clone(), module)
-    }).collect();
+    let mut modules = parse(input)?;
 
     let mut dp_presses = 1;
 
@@ -258,7 +273,12 @@ pub fn part_two(input: &str) -> Option<usize> {
         dp_presses += 1
     }
 
-    Some(dh_presses * qd_presses * bb_presses * dp_presses)
+    // each counter is assumed to reset right after sending its high pulse, so it sends one every `presses` presses.
+    let pulses = [dh_presses, qd_presses, bb_presses, dp_presses].map(|presses| Periodic::new(presses, presses));
+    let result = Periodic::intersect_all(pulses)
+        .ok_or_else(|| ParseError::new("the counters never send a high pulse at the same time"))?;
+
+    Ok(result.offset)
 }
 
 #[cfg(test)]
@@ -268,12 +288,23 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11687500));
+        assert_eq!(result.unwrap(), 11687500);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result.ok(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            broadcaster -> a\n\
+            %a -> inv, con\n\
+            &inv -> b\n\
+            %b -> con\n\
+            &con -> output");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
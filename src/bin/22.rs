@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(22);
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -10,19 +11,34 @@ struct Brick {
 }
 
 impl Brick {
-    fn create(s: &str, i: usize) -> Self {
-        let (from_part, to_part) = s.split('~').tuples().next().unwrap();
-        let from = Position::from_str(from_part).unwrap();
-        let to = Position::from_str(to_part).unwrap();
+    fn parse(s: &str, i: usize) -> Result<Self, ParseError> {
+        let Some((from_part, to_part)) = s.split_once('~') else {
+            return Err(ParseError::at(s, s, "expected two positions separated by '~'"));
+        };
 
-        Brick {
+        let from = Position::from_str(from_part).map_err(|e| e.within(s, from_part))?;
+        let to = Position::from_str(to_part).map_err(|e| e.within(s, to_part))?;
+
+        if from.x > to.x || from.y > to.y || from.z > to.z {
+            return Err(ParseError::at(s, to_part, "expected the brick to end after its start"));
+        }
+
+        Ok(Brick {
             id: i,
             from,
             to,
-        }
+        })
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| Brick::parse(line, line_idx).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 struct Position {
     x: usize,
@@ -31,16 +47,16 @@ struct Position {
 }
 
 impl FromStr for Position {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let coords: Vec<usize> = s.split(',').map(|c| c.parse::<usize>().unwrap()).collect();
+        let coords: Vec<usize> = s.split(',').map(|c| parse_at(s, c)).collect::<Result<_, _>>()?;
 
-        Ok(Position {
-            x: coords[0],
-            y: coords[1],
-            z: coords[2],
-        })
+        let [x, y, z] = coords[..] else {
+            return Err(ParseError::at(s, s, format!("expected 3 coordinates, found {}", coords.len())));
+        };
+
+        Ok(Position { x, y, z })
     }
 }
 
@@ -94,11 +110,9 @@ fn fall(bricks: &[Brick]) -> (Vec<Brick>, usize) {
     (fallen_bricks, fallen_count)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let bricks: Vec<Brick> = input
-        .lines()
-        .enumerate()
-        .map(|(line_idx, line)| Brick::create(line, line_idx))
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let bricks: Vec<Brick> = parse(input)?
+        .into_iter()
         .sorted_by(|a, b| a.from.z.cmp(&b.from.z))
         .collect();
 
@@ -124,14 +138,12 @@ pub fn part_one(input: &str) -> Option<usize> {
         None
     });
 
-    Some(result.count())
+    Ok(result.count())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let bricks: Vec<Brick> = input
-        .lines()
-        .enumerate()
-        .map(|(line_idx, line)| Brick::create(line, line_idx))
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let bricks: Vec<Brick> = parse(input)?
+        .into_iter()
         .sorted_by(|a, b| a.from.z.cmp(&b.from.z))
         .collect();
 
@@ -162,7 +174,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         fall(&modified_bricks).1
     }).sum();
 
-    Some(result)
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -172,12 +184,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
+        assert_eq!(result.unwrap(), 5);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            1,0,1~1,2,1\n\
+            0,0,2~2,0,2\n\
+            0,2,3~2,2,3");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
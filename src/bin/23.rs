@@ -1,6 +1,6 @@
-use std::collections::HashMap;
 use pathfinding::matrix::{directions, Matrix};
 use rayon::prelude::{*};
+use advent_of_code::{Graph, NodeId, ParseError};
 advent_of_code::solution!(23);
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -11,23 +11,23 @@ enum Tile {
 }
 
 impl Tile {
-    fn create_part_1(value: char) -> Self {
+    fn create_part_1(value: char) -> Result<Self, ParseError> {
         match value {
-            '#' => Tile::Forest,
-            '.' => Tile::Path,
-            '^' => Tile::Slope(directions::N),
-            'v' => Tile::Slope(directions::S),
-            '<' => Tile::Slope(directions::W),
-            '>' => Tile::Slope(directions::E),
-            _ => panic!("unexpected tile"),
+            '#' => Ok(Tile::Forest),
+            '.' => Ok(Tile::Path),
+            '^' => Ok(Tile::Slope(directions::N)),
+            'v' => Ok(Tile::Slope(directions::S)),
+            '<' => Ok(Tile::Slope(directions::W)),
+            '>' => Ok(Tile::Slope(directions::E)),
+            x => Err(ParseError::new(format!("unexpected tile {x:?}"))),
         }
     }
 
-    fn create_part_2(value: char) -> Self {
+    fn create_part_2(value: char) -> Result<Self, ParseError> {
         match value {
-            '#' => Tile::Forest,
-            '.' | '^' | 'v' | '<' | '>' => Tile::Path,
-            _ => panic!("unexpected tile"),
+            '#' => Ok(Tile::Forest),
+            '.' | '^' | 'v' | '<' | '>' => Ok(Tile::Path),
+            x => Err(ParseError::new(format!("unexpected tile {x:?}"))),
         }
     }
 }
@@ -38,14 +38,28 @@ struct Map {
 }
 
 impl Map {
-    fn create_part_1(s: &str) -> Self {
-        let grid = s.lines().map(|line| line.chars().map(Tile::create_part_1)).collect();
-        Map { grid }
+    fn create_part_1(s: &str) -> Result<Self, ParseError> {
+        Map::parse(s, Tile::create_part_1)
     }
 
-    fn create_part_2(s: &str) -> Self {
-        let grid = s.lines().map(|line| line.chars().map(Tile::create_part_2)).collect();
-        Map { grid }
+    fn create_part_2(s: &str) -> Result<Self, ParseError> {
+        Map::parse(s, Tile::create_part_2)
+    }
+
+    fn parse(s: &str, create_tile: fn(char) -> Result<Tile, ParseError>) -> Result<Self, ParseError> {
+        let rows: Vec<Vec<Tile>> = s.lines().map(|line| {
+            line.char_indices()
+                .map(|(idx, c)| create_tile(c).map_err(|e| e.within(s, &line[idx..])))
+                .collect()
+        }).collect::<Result<_, _>>()?;
+
+        let grid = Matrix::from_rows(rows).map_err(|e| ParseError::new("rows differ in width").with_source(e))?;
+
+        if grid.columns < 2 || grid.get((0, 1)) != Some(&Tile::Path) || grid.get((grid.rows - 1, grid.columns - 2)) != Some(&Tile::Path) {
+            return Err(ParseError::new("expected paths at the start and the finish"));
+        }
+
+        Ok(Map { grid })
     }
 }
 
@@ -59,12 +73,6 @@ struct State {
 
 type Position = (usize, usize);
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Node {
-    position: Position,
-    to: Vec<(Position, usize)>,
-}
-
 fn calc(map: Map, start: State, finish_pos: (usize, usize)) -> usize {
     let mut current_states = vec!(start);
     let mut finished_states = vec!();
@@ -119,121 +127,62 @@ fn get_next_states(state: &State, map: &Map) -> Vec<State> {
         .collect::<Vec<_>>()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = Map::create_part_1(input);
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let map = Map::create_part_1(input)?;
     let start = State { position: (0, 1), direction: (1, 0), distance: 0, tile: Tile::Path };
     let finish_pos = (map.grid.rows - 1, map.grid.columns - 2);
     let result = calc(map, start, finish_pos);
-    Some(result)
-}
-
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = Map::create_part_2(input);
-    let structure: HashMap<Position, Node> = get_structure(&map);
-    let result: Vec<_> = find_longest(&map, (0, 1), &structure, 0, vec!());
-    Some(*result.iter().max().unwrap() + 1)
+    Ok(result)
 }
 
-fn get_structure(map: &Map) -> HashMap<Position, Node> {
-    let mut paths = vec!();
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let map = Map::create_part_2(input)?;
+    let (start, finish) = ((0, 1), (map.grid.rows - 1, map.grid.columns - 2));
 
-    for row in 0..map.grid.rows {
-        for col in 0..map.grid.columns {
-            if map.grid.get((row, col)).unwrap() == &Tile::Path { paths.push((row, col)); }
-        }
-    }
-
-    let junctions = paths.iter().filter(|p| get_next_states_2(**p, 0, None, map).len() > 2);
-
-    let mut result: HashMap<Position, Node> = junctions.map(|j| {
-        let next_junctions = find_next_junctions(map, *j, (0, 1), (map.grid.rows - 1, map.grid.columns - 2));
-        (*j, Node { position: *j, to: next_junctions.iter().map(|j| (j.0.position, j.1)).collect() })
-    }).collect();
-
-    result.insert((0, 1), Node { position: (0, 1), to: find_next_junctions(map, (0, 1), (0, 1), (map.grid.rows - 1, map.grid.columns - 2)).iter().map(|j| (j.0.position, j.1)).collect() });
-    result.insert((map.grid.rows - 1, map.grid.columns - 2), Node { position: (map.grid.rows - 1, map.grid.columns - 2), to: find_next_junctions(map, (map.grid.rows - 1, map.grid.columns - 2), (0, 1), (map.grid.rows - 1, map.grid.columns - 2)).iter().map(|j| (j.0.position, j.1)).collect() });
-
-    result
-}
+    let junctions = get_structure(&map).contract_corridors(|_| false);
+    let (Some(start), Some(finish)) = (junctions.id(&start), junctions.id(&finish)) else {
+        return Err(ParseError::new("the start and the finish are not on the same path"));
+    };
 
-fn get_next_states_2(position: Position, distance: usize, starting_direction: Option<(isize, isize)>, map: &Map) -> Vec<State> {
-    [directions::N, directions::E, directions::S, directions::W]
-        .iter()
-        .flat_map(|direction| {
-            // get all neighboring points within the grid
-            map.grid.move_in_direction(position, *direction)
-                .map(|position| (position, *direction, *map.grid.get(position).expect("tile at position must exist")))
-        })
-        .filter(|(_, direction, _)| {
-            // do not allow directions that would backtrack
-            match starting_direction {
-                Some(dir) => !(dir.0 == -direction.0 && dir.1 == -direction.1),
-                None => true,
-            }
-        })
-        .filter(|(_, _, tile)| {
-            // only allow directions that lead to a path
-            matches!(tile, Tile::Path)
-        })
-        .map(|(position, direction, tile)| {
-            // return successors
-            State { position, direction, distance: distance + 1, tile }
-        })
-        .collect::<Vec<_>>()
+    let mut visited = vec![false; junctions.len()];
+    find_longest(&junctions, start, finish, &mut visited).ok_or_else(|| ParseError::new("no path to the finish found"))
 }
 
-fn find_next_junctions(map: &Map, junction: Position, start_pos: (usize, usize), finish_pos: (usize, usize)) -> Vec<(State, usize)> {
-    let mut result = vec!();
+/// Returns the graph of all path tiles, connected to their neighbouring path tiles.
+fn get_structure(map: &Map) -> Graph<Position> {
+    let mut graph = Graph::new();
 
-    let next_states = get_next_states_2(junction, 0, None, map);
+    for position in map.grid.keys().filter(|p| map.grid[*p] == Tile::Path) {
+        let id = graph.add_node(position);
 
-    for way in next_states {
-        if way.position == finish_pos || way.position == start_pos {
-            result.push((way.clone(), 1));
-            continue;
-        }
-        let mut next_states = get_next_states(&way, map);
-        if next_states.is_empty() { continue; }
-        let mut current_weight = 1;
-        let mut base = next_states[0].clone();
-
-        while next_states.len() == 1 {
-            base = next_states[0].clone();
-            if base.position == finish_pos || base.position == start_pos {
-                result.push((base.clone(), current_weight));
-                break;
+        for neighbour in [directions::S, directions::E].iter().filter_map(|d| map.grid.move_in_direction(position, *d)) {
+            if map.grid[neighbour] == Tile::Path {
+                let neighbour_id = graph.add_node(neighbour);
+                graph.add_undirected_edge(id, neighbour_id, 1);
             }
-            next_states = get_next_states(&base, map);
-            current_weight += 1;
-        }
-
-        if next_states.len() > 1 {
-            result.push((base.clone(), current_weight));
         }
     }
 
-    result
+    graph
 }
 
-fn find_longest(map: &Map, position: Position, structure: &HashMap<Position, Node>, current_weight: usize, visited: Vec<Position>) -> Vec<usize> {
-    let node = structure.get(&position).unwrap();
-
-    // finish
-    if node.position == (map.grid.rows - 1, map.grid.columns - 2) {
-        return vec!(current_weight);
+fn find_longest(graph: &Graph<Position>, position: NodeId, finish: NodeId, visited: &mut [bool]) -> Option<usize> {
+    if position == finish {
+        return Some(0);
     }
 
-    let mut result = vec!();
+    visited[position] = true;
 
-    for to in &node.to {
-        if !visited.contains(&to.0) {
-            for x in find_longest(map, to.0, structure, current_weight + to.1, [visited.as_slice(), vec!(position).as_slice()].concat()) {
-                result.push(x);
-            }
+    let mut longest = None;
+    for &(to, weight) in graph.neighbours(position) {
+        if !visited[to] {
+            let distance = find_longest(graph, to, finish, visited).map(|d| d + weight as usize);
+            longest = longest.max(distance);
         }
     }
 
-    result
+    visited[position] = false;
+    longest
 }
 
 #[cfg(test)]
@@ -243,12 +192,22 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(94));
+        assert_eq!(result.unwrap(), 94);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(154));
+        assert_eq!(result.unwrap(), 154);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            #.###\n\
+            #...#\n\
+            ###.#");
+        advent_of_code::template::fuzz::check_parser(&input, Map::create_part_1);
+        advent_of_code::template::fuzz::check_parser(&input, Map::create_part_2);
     }
 }
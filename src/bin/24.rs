@@ -1,6 +1,7 @@
 use std::str::FromStr;
 use intersect2d::{intersect, Intersection};
 use itertools::Itertools;
+use advent_of_code::{parse_at, ParseError};
 advent_of_code::solution!(24);
 
 #[derive(Debug, Copy, Clone)]
@@ -10,21 +11,38 @@ struct Hailstone {
 }
 
 impl FromStr for Hailstone {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (pos_part, vel_part) = s.split('@').tuples().next().unwrap();
+        let Some((pos_part, vel_part)) = s.split_once('@') else {
+            return Err(ParseError::at(s, s, "expected a position and a velocity separated by '@'"));
+        };
+
+        let parse_triple = |part: &str| -> Result<(f64, f64, f64), ParseError> {
+            let values: Vec<f64> = part.split(',').map(|x| parse_at(s, x.trim())).collect::<Result<_, _>>()?;
+            match values[..] {
+                [x, y, z] => Ok((x, y, z)),
+                _ => Err(ParseError::at(s, part, format!("expected 3 values, found {}", values.len()))),
+            }
+        };
 
-        let positions: Vec<_> = pos_part.split(',').map(|x| x.trim().parse::<f64>().unwrap()).collect();
-        let velocities: Vec<_> = vel_part.split(',').map(|x| x.trim().parse::<f64>().unwrap()).collect();
+        let (px, py, pz) = parse_triple(pos_part)?;
+        let (vx, vy, vz) = parse_triple(vel_part)?;
 
         Ok(Hailstone {
-            position: Position(positions[0], positions[1], positions[2]),
-            velocity: Velocity(velocities[0], velocities[1], velocities[2]),
+            position: Position(px, py, pz),
+            velocity: Velocity(vx, vy, vz),
         })
     }
 }
 
+fn parse(input: &str) -> Result<Vec<Hailstone>, ParseError> {
+    input
+        .lines()
+        .map(|line| Hailstone::from_str(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
 #[derive(Debug, Copy, Clone)]
 struct Position(f64, f64, f64);
 
@@ -41,13 +59,13 @@ fn get_positions_range(hailstone: &Hailstone) -> (Position, Position) {
     (pos_a, pos_b)
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
     let min = 200000000000000.;
     let max = 400000000000000.;
 
-    let hailstones = input.lines().map(|line| Hailstone::from_str(line).unwrap());
+    let hailstones = parse(input)?;
 
-    let x: Vec<_> = hailstones.map(|stone| get_positions_range(&stone)).collect();
+    let x: Vec<_> = hailstones.iter().map(get_positions_range).collect();
 
     let mut intersections = vec!();
 
@@ -62,13 +80,15 @@ pub fn part_one(input: &str) -> Option<usize> {
         }
     }
 
-    Some(intersections.len())
+    Ok(intersections.len())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let hailstones = input.lines().map(|line| Hailstone::from_str(line).unwrap());
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let hailstones = parse(input)?;
 
-    let (stone_a, stone_b, stone_c) = hailstones.take(3).collect_tuple().unwrap();
+    let Some((stone_a, stone_b, stone_c)) = hailstones.into_iter().take(3).collect_tuple() else {
+        return Err(ParseError::new("expected at least 3 hailstones"));
+    };
 
     let ext_2 = [
         exterior2(&subtract_velocities(&stone_a.velocity, &stone_b.velocity), &subtract_positions(&stone_a.position, &stone_b.position)),
@@ -115,7 +135,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let y = det_ay / det_a;
     let z = det_az / det_a;
 
-    Some((x.ceil() + y.ceil() + z.ceil()) as usize - 2)
+    Ok((x.ceil() + y.ceil() + z.ceil()) as usize - 2)
 }
 
 fn subtract_velocities(a: &Velocity, b: &Velocity) -> Velocity {
@@ -146,12 +166,20 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result.unwrap(), 2);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(47));
+        assert_eq!(result.unwrap(), 47);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = advent_of_code::template::fuzz::example_or(DAY, "\
+            19, 13, 30 @ -2,  1, -2\n\
+            18, 19, 22 @ -1, -1, -2");
+        advent_of_code::template::fuzz::check_parser(&input, parse);
     }
 }
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of a sequence `x0, f(x0), f(f(x0)), ...` of a deterministic step function `f`: once a state
+/// repeats, the sequence loops. The states from step `start` on repeat every `period` steps.
+///
+/// All detection functions run the step function until the sequence repeats, they do not return if it never does.
+///
+/// ```
+/// # use advent_of_code::Cycle;
+/// // 3, 6, 2, 4, 8, 6, 2, ...
+/// let double = |x: &u32| x * 2 % 10;
+/// let cycle = Cycle::brent(3, double);
+/// assert_eq!(cycle, Cycle { start: 1, period: 4 });
+/// assert_eq!(cycle.nth(3, double, 1_000_000_000), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// Number of steps before the loop is entered.
+    pub start: usize,
+    /// Length of the loop.
+    pub period: usize,
+}
+
+impl Cycle {
+    /// Finds the cycle with Brent's algorithm, which keeps only two states at a time.
+    pub fn brent<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Self {
+        // find the period by moving the hare until it meets the tortoise, which jumps ahead in powers of two.
+        let (mut power, mut period) = (1, 1);
+        let mut tortoise = initial.clone();
+        let mut hare = step(&initial);
+
+        while tortoise != hare {
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare = step(&hare);
+            period += 1;
+        }
+
+        // with the hare one period ahead, both meet at the start of the loop.
+        let mut tortoise = initial.clone();
+        let mut hare = initial;
+        for _ in 0..period {
+            hare = step(&hare);
+        }
+
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+
+        Self { start, period }
+    }
+
+    /// Finds the cycle with Floyd's algorithm, which keeps only two states at a time.
+    pub fn floyd<T: Clone + PartialEq>(initial: T, mut step: impl FnMut(&T) -> T) -> Self {
+        // the hare moves twice as fast, they meet somewhere within the loop.
+        let mut tortoise = step(&initial);
+        let mut hare = step(&tortoise);
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            let next = step(&hare);
+            hare = step(&next);
+        }
+
+        // the distance from the meeting point to the start of the loop equals the one from the initial state.
+        let mut start = 0;
+        tortoise = initial;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+
+        let mut period = 1;
+        hare = step(&tortoise);
+        while tortoise != hare {
+            hare = step(&hare);
+            period += 1;
+        }
+
+        Self { start, period }
+    }
+
+    /// Finds the cycle by remembering every state, which calls `step` the fewest times.
+    /// The returned states make jumping to any step a lookup.
+    pub fn hashed<T: Clone + Eq + Hash>(
+        initial: T,
+        mut step: impl FnMut(&T) -> T,
+    ) -> CycleStates<T> {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut state = initial;
+
+        loop {
+            if let Some(&start) = seen.get(&state) {
+                let cycle = Self {
+                    start,
+                    period: states.len() - start,
+                };
+                return CycleStates { cycle, states };
+            }
+
+            seen.insert(state.clone(), states.len());
+            let next = step(&state);
+            states.push(state);
+            state = next;
+        }
+    }
+
+    /// Returns the earliest step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+
+    /// Returns the state after `n` steps, running at most `start + period` of them.
+    pub fn nth<T>(&self, initial: T, mut step: impl FnMut(&T) -> T, n: usize) -> T {
+        (0..self.equivalent_step(n)).fold(initial, |state, _| step(&state))
+    }
+}
+
+/// A [`Cycle`] together with the states of the sequence up to the end of its first loop.
+#[derive(Debug, Clone)]
+pub struct CycleStates<T> {
+    pub cycle: Cycle,
+    states: Vec<T>,
+}
+
+impl<T> CycleStates<T> {
+    /// Returns the state after `n` steps.
+    pub fn nth(&self, n: usize) -> &T {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+
+    /// Returns the distinct states, from the initial one to the last before the loop repeats.
+    pub fn states(&self) -> &[T] {
+        &self.states
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Cycle;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn detects_cycles() {
+        // 3, 6, 2, 4, 8, 6, ... mod 10
+        let double = |x: &u32| x * 2 % 10;
+        let expected = Cycle {
+            start: 1,
+            period: 4,
+        };
+        assert_eq!(Cycle::brent(3, double), expected);
+        assert_eq!(Cycle::floyd(3, double), expected);
+        assert_eq!(Cycle::hashed(3, double).cycle, expected);
+
+        // a fixed point right away.
+        let fixed = Cycle {
+            start: 0,
+            period: 1,
+        };
+        assert_eq!(Cycle::brent(0, double), fixed);
+        assert_eq!(Cycle::floyd(0, double), fixed);
+        assert_eq!(Cycle::hashed(0, double).cycle, fixed);
+    }
+
+    #[test]
+    fn jumps_to_steps() {
+        let double = |x: &u32| x * 2 % 10;
+        let cycle = Cycle::brent(3, double);
+        assert_eq!(
+            [0, 1, 4, 5, 9].map(|n| cycle.equivalent_step(n)),
+            [0, 1, 4, 1, 1]
+        );
+        assert_eq!(cycle.nth(3, double, 0), 3);
+        assert_eq!(cycle.nth(3, double, 1_000_000_002), 2);
+
+        let states = Cycle::hashed(3, double);
+        assert_eq!(states.states(), [3, 6, 2, 4, 8]);
+        assert_eq!(*states.nth(1_000_000_002), 2);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2023);
+
+        for _ in 0..200 {
+            // a random function on a small domain always runs into a cycle.
+            let size = rng.gen_range(1..50);
+            let function: Vec<usize> = (0..size).map(|_| rng.gen_range(0..size)).collect();
+            let step = |x: &usize| function[*x];
+            let initial = rng.gen_range(0..size);
+
+            let mut sequence = vec![initial];
+            let start = loop {
+                let next = step(sequence.last().unwrap());
+                if let Some(start) = sequence.iter().position(|x| *x == next) {
+                    break start;
+                }
+                sequence.push(next);
+            };
+            let expected = Cycle {
+                start,
+                period: sequence.len() - start,
+            };
+
+            assert_eq!(
+                Cycle::brent(initial, step),
+                expected,
+                "{function:?} from {initial}"
+            );
+            assert_eq!(
+                Cycle::floyd(initial, step),
+                expected,
+                "{function:?} from {initial}"
+            );
+
+            let states = Cycle::hashed(initial, step);
+            assert_eq!(states.cycle, expected, "{function:?} from {initial}");
+            assert_eq!(states.states(), sequence);
+
+            let n = rng.gen_range(0..1000);
+            let nth = (0..n).fold(initial, |x, _| step(&x));
+            assert_eq!(expected.nth(initial, step, n), nth);
+            assert_eq!(*states.nth(n), nth);
+        }
+    }
+}
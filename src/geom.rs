@@ -0,0 +1,443 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::ParseError;
+
+/// A position on a 2D plane. `y` grows downwards, as rows are counted in the puzzle input.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A difference between two [`Point`]s, e.g. a single step or a movement.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A position in 3D space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// A difference between two [`Point3`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// Implements the arithmetic between a point and vector type with the given fields:
+/// `point + vector`, `point - vector`, `point - point`, `vector + vector`, `vector * n` and `-vector`.
+macro_rules! impl_ops {
+    ($point:ident, $vector:ident, $($field:ident),+) => {
+        impl Add<$vector> for $point {
+            type Output = $point;
+
+            fn add(self, rhs: $vector) -> $point {
+                $point { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl AddAssign<$vector> for $point {
+            fn add_assign(&mut self, rhs: $vector) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub<$vector> for $point {
+            type Output = $point;
+
+            fn sub(self, rhs: $vector) -> $point {
+                $point { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl SubAssign<$vector> for $point {
+            fn sub_assign(&mut self, rhs: $vector) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vector;
+
+            fn sub(self, rhs: $point) -> $vector {
+                $vector { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Add for $vector {
+            type Output = $vector;
+
+            fn add(self, rhs: $vector) -> $vector {
+                $vector { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl AddAssign for $vector {
+            fn add_assign(&mut self, rhs: $vector) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub for $vector {
+            type Output = $vector;
+
+            fn sub(self, rhs: $vector) -> $vector {
+                $vector { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<isize> for $vector {
+            type Output = $vector;
+
+            fn mul(self, rhs: isize) -> $vector {
+                $vector { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $vector {
+            type Output = $vector;
+
+            fn neg(self) -> $vector {
+                $vector { $($field: -self.$field),+ }
+            }
+        }
+
+        impl $point {
+            /// Returns the distance to `other` along the axes.
+            pub fn manhattan(self, other: $point) -> usize {
+                (self - other).manhattan()
+            }
+        }
+
+        impl $vector {
+            /// Returns the length of the vector along the axes.
+            pub fn manhattan(self) -> usize {
+                0 $(+ self.$field.unsigned_abs())+
+            }
+        }
+    };
+}
+
+impl_ops!(Point, Vector, x, y);
+impl_ops!(Point3, Vector3, x, y, z);
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the point one step in `direction`.
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.delta()
+    }
+
+    /// Returns the four orthogonal neighbours, in the order of [`Direction::ALL`].
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// Returns the eight neighbours including diagonals, in the order of [`Direction8::ALL`].
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self + d.delta())
+    }
+}
+
+impl Vector {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Rotates the vector by 90 degrees, counterclockwise as seen on screen.
+    pub fn turn_left(self) -> Vector {
+        Vector::new(self.y, -self.x)
+    }
+
+    /// Rotates the vector by 90 degrees, clockwise as seen on screen.
+    pub fn turn_right(self) -> Vector {
+        Vector::new(-self.y, self.x)
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the six neighbours sharing a face.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        [
+            Vector3::new(1, 0, 0),
+            Vector3::new(-1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, -1, 0),
+            Vector3::new(0, 0, 1),
+            Vector3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |delta| self + delta)
+    }
+}
+
+impl Vector3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Converts a [`Grid`](crate::Grid) position.
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as isize, y as isize)
+    }
+}
+
+/// Converts to a signed [`Grid`](crate::Grid) position, e.g. for [`Grid::get_signed`](crate::Grid::get_signed).
+impl From<Point> for (isize, isize) {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<Vector> for (isize, isize) {
+    fn from(vector: Vector) -> Self {
+        (vector.x, vector.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+///
+/// ```
+/// # use advent_of_code::{Direction, Point};
+/// let dir = Direction::try_from('^').unwrap();
+/// assert_eq!(dir.turn_right(), Direction::Right);
+/// assert_eq!(Point::new(2, 2) + dir.delta() * 2, Point::new(2, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Direction {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Returns the vector of a single step.
+    pub fn delta(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+}
+
+/// Parses `U`, `R`, `D`, `L` or the arrows `^`, `>`, `v`, `<`.
+impl TryFrom<char> for Direction {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | '^' => Ok(Direction::Up),
+            'R' | '>' => Ok(Direction::Right),
+            'D' | 'v' => Ok(Direction::Down),
+            'L' | '<' => Ok(Direction::Left),
+            x => Err(ParseError::new(format!("invalid direction {x:?}"))),
+        }
+    }
+}
+
+/// One of the eight directions including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise from `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Turns by 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Direction8 {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turns by 45 degrees clockwise.
+    pub fn turn_right(self) -> Direction8 {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Direction8 {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Returns the vector of a single step.
+    pub fn delta(self) -> Vector {
+        match self {
+            Direction8::Up => Vector::new(0, -1),
+            Direction8::UpRight => Vector::new(1, -1),
+            Direction8::Right => Vector::new(1, 0),
+            Direction8::DownRight => Vector::new(1, 1),
+            Direction8::Down => Vector::new(0, 1),
+            Direction8::DownLeft => Vector::new(-1, 1),
+            Direction8::Left => Vector::new(-1, 0),
+            Direction8::UpLeft => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Direction8, Point, Point3, Vector, Vector3};
+
+    #[test]
+    fn computes_with_points() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - Vector::new(1, 1), Point::new(0, 1));
+        assert_eq!(-Vector::new(1, -1) * 3, Vector::new(-3, 3));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(Point::ORIGIN.manhattan(Point::new(-3, -4)), 7);
+
+        let mut p = Point::ORIGIN;
+        p += Vector::new(2, 3);
+        p -= Vector::new(1, 1);
+        assert_eq!(p, Point::from((1, 2)));
+        assert_eq!(<(isize, isize)>::from(p), (1, 2));
+
+        let v = Vector::new(1, -2);
+        assert_eq!(v.turn_right(), Vector::new(2, 1));
+        assert_eq!(v.turn_left(), Vector::new(-2, -1));
+        assert_eq!(v.turn_left().turn_left(), -v);
+
+        let (a, b) = (Point3::new(1, 2, 3), Point3::new(-1, 2, 6));
+        assert_eq!(b - a, Vector3::new(-2, 0, 3));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(
+            Point3::ORIGIN
+                .neighbours6()
+                .filter(|n| n.manhattan(Point3::ORIGIN) == 1)
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().delta(), dir.delta().turn_right());
+            assert_eq!(dir.reverse().delta(), -dir.delta());
+            assert_eq!(Direction8::from(dir).delta(), dir.delta());
+        }
+
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownRight.reverse(), Direction8::UpLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert_eq!(
+            Direction8::ALL
+                .iter()
+                .filter(|d| d.delta().manhattan() == 2)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn parses_directions() {
+        let parsed = "URDL^>v<"
+            .chars()
+            .map(Direction::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(parsed, [Direction::ALL, Direction::ALL].concat());
+        assert_eq!(
+            Direction::try_from('x').unwrap_err().message(),
+            "invalid direction 'x'"
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let p = Point::new(5, 5);
+        let n4: Vec<_> = p.neighbours4().collect();
+        assert_eq!(
+            n4,
+            [
+                Point::new(5, 4),
+                Point::new(6, 5),
+                Point::new(5, 6),
+                Point::new(4, 5)
+            ]
+        );
+        assert_eq!(p.neighbours8().count(), 8);
+        assert!(p
+            .neighbours8()
+            .all(|n| n != p && (n - p).x.abs() <= 1 && (n - p).y.abs() <= 1));
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::ops::Range;
+
+/// Index of a node within a [`Graph`], assigned in the order the nodes were added.
+pub type NodeId = usize;
+
+/// A graph with labelled nodes and weighted edges, stored as adjacency lists.
+///
+/// Labels are interned: each distinct label is assigned a [`NodeId`] once, algorithms work on the ids.
+/// Undirected graphs store each edge in both directions.
+///
+/// ```
+/// # use advent_of_code::Graph;
+/// let graph = Graph::undirected([("a", "b"), ("b", "c"), ("d", "e")]);
+/// assert_eq!(graph.len(), 5);
+/// assert_eq!(graph.components().len(), 2);
+/// let b = graph.id(&"b").unwrap();
+/// assert_eq!(graph.neighbours(b).len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    labels: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, u64)>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self {
+            labels: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// Creates a directed graph with an edge of weight 1 per pair.
+    pub fn directed(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in edges {
+            let (from, to) = (graph.add_node(from), graph.add_node(to));
+            graph.add_edge(from, to, 1);
+        }
+        graph
+    }
+
+    /// Creates an undirected graph with an edge of weight 1 per pair.
+    pub fn undirected(edges: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self::new();
+        for (a, b) in edges {
+            let (a, b) = (graph.add_node(a), graph.add_node(b));
+            graph.add_undirected_edge(a, b, 1);
+        }
+        graph
+    }
+
+    /// Returns the id of the node with `label`, adding the node if it does not exist yet.
+    pub fn add_node(&mut self, label: N) -> NodeId {
+        if let Some(id) = self.ids.get(&label) {
+            return *id;
+        }
+
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, weight: u64) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn id(&self, label: &N) -> Option<NodeId>
+    where
+        N: Eq + Hash,
+    {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: NodeId) -> &N {
+        &self.labels[id]
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn nodes(&self) -> Range<NodeId> {
+        0..self.len()
+    }
+
+    /// Returns the outgoing edges of a node with their weights, in the order they were added.
+    pub fn neighbours(&self, id: NodeId) -> &[(NodeId, u64)] {
+        &self.edges[id]
+    }
+
+    /// Returns all edges as `(from, to, weight)`. Undirected edges appear once per direction.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, u64)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |&(to, weight)| (from, to, weight)))
+    }
+
+    /// Removes the nodes of corridors, i.e. nodes with exactly two edges, and joins their edges into one
+    /// with the summed weight. Nodes for which `keep` returns `true` are never removed.
+    /// Meant for undirected graphs, e.g. to reduce a maze to its junctions.
+    pub fn contract_corridors(&self, keep: impl Fn(NodeId) -> bool) -> Graph<N>
+    where
+        N: Clone + Eq + Hash,
+    {
+        let kept: Vec<bool> = self
+            .nodes()
+            .map(|id| self.edges[id].len() != 2 || keep(id))
+            .collect();
+
+        let mut graph = Graph::new();
+        for id in self.nodes().filter(|id| kept[*id]) {
+            graph.add_node(self.labels[id].clone());
+        }
+
+        for from in self.nodes().filter(|id| kept[*id]) {
+            for &(next, weight) in &self.edges[from] {
+                let (mut prev, mut current, mut distance) = (from, next, weight);
+
+                while !kept[current] {
+                    let Some(&(next, weight)) =
+                        self.edges[current].iter().find(|(to, _)| *to != prev)
+                    else {
+                        break;
+                    };
+                    (prev, current, distance) = (current, next, distance + weight);
+                }
+
+                if kept[current] && current != from {
+                    let (a, b) = (
+                        graph.ids[&self.labels[from]],
+                        graph.ids[&self.labels[current]],
+                    );
+                    graph.add_edge(a, b, distance);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the sets of nodes connected to each other, following edges in both directions.
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut undirected = self.edges.clone();
+        for (from, to, weight) in self.edges() {
+            undirected[to].push((from, weight));
+        }
+
+        let mut visited = vec![false; self.len()];
+        let mut components = vec![];
+
+        for start in self.nodes() {
+            if visited[start] {
+                continue;
+            }
+
+            let mut members = vec![];
+            let mut queue = VecDeque::from([start]);
+            visited[start] = true;
+
+            while let Some(id) = queue.pop_front() {
+                members.push(id);
+                for &(next, _) in &undirected[id] {
+                    if !visited[next] {
+                        visited[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            components.push(members);
+        }
+
+        components
+    }
+
+    /// Orders the nodes so that every edge points forward, `None` if the graph has a cycle.
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0; self.len()];
+        for (_, to, _) in self.edges() {
+            incoming[to] += 1;
+        }
+
+        let mut queue: VecDeque<NodeId> = self.nodes().filter(|id| incoming[*id] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &(next, _) in &self.edges[id] {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Finds a minimum cut of an undirected graph with the Stoer-Wagner algorithm, i.e. the edges of least total
+    /// weight that split it in two. Returns that weight and the nodes on one side, `None` for less than two nodes.
+    pub fn min_cut(&self) -> Option<(u64, Vec<NodeId>)> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        // merged nodes are represented by the node they were merged into.
+        let mut weights: Vec<HashMap<NodeId, u64>> = vec![HashMap::new(); self.len()];
+        for (from, to, weight) in self.edges().filter(|(from, to, _)| from != to) {
+            *weights[from].entry(to).or_default() += weight;
+        }
+
+        let mut members: Vec<Vec<NodeId>> = self.nodes().map(|id| vec![id]).collect();
+        let mut active: Vec<NodeId> = self.nodes().collect();
+        let mut best: Option<(u64, Vec<NodeId>)> = None;
+
+        while active.len() > 1 {
+            // maximum adjacency search: repeatedly add the node most tightly connected to the added ones.
+            let mut connection = vec![0; self.len()];
+            let mut added = vec![false; self.len()];
+            let mut heap = BinaryHeap::from([(0, active[0])]);
+            let mut order = vec![];
+
+            while let Some((weight, id)) = heap.pop() {
+                if added[id] || weight != connection[id] {
+                    continue;
+                }
+
+                added[id] = true;
+                order.push(id);
+
+                for (&next, &w) in &weights[id] {
+                    if !added[next] {
+                        connection[next] += w;
+                        heap.push((connection[next], next));
+                    }
+                }
+            }
+
+            if order.len() < active.len() {
+                // the graph is not connected, the component found is a cut of weight 0.
+                let side = order.iter().flat_map(|id| members[*id].clone()).collect();
+                return Some((0, side));
+            }
+
+            let (s, t) = (order[order.len() - 2], order[order.len() - 1]);
+            if best
+                .as_ref()
+                .is_none_or(|(weight, _)| connection[t] < *weight)
+            {
+                best = Some((connection[t], members[t].clone()));
+            }
+
+            // merge t into s.
+            let t_weights = std::mem::take(&mut weights[t]);
+            for (next, w) in t_weights {
+                weights[next].remove(&t);
+                if next != s {
+                    *weights[s].entry(next).or_default() += w;
+                    *weights[next].entry(s).or_default() += w;
+                }
+            }
+            let t_members = std::mem::take(&mut members[t]);
+            members[s].extend(t_members);
+            active.retain(|id| *id != t);
+        }
+
+        best
+    }
+
+    /// Renders the graph in the DOT language of Graphviz, e.g. for `dot -Tsvg`.
+    /// Edges of undirected graphs are printed once, weights other than 1 as edge labels.
+    pub fn to_dot(&self, directed: bool) -> String
+    where
+        N: Display,
+    {
+        let (keyword, arrow) = if directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut dot = format!("{keyword} {{\n");
+
+        for id in self.nodes() {
+            let label = self.labels[id].to_string().replace('"', "\\\"");
+            let _ = writeln!(dot, "  {id} [label=\"{label}\"];");
+        }
+
+        let mut undirected_seen: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+        for (from, to, weight) in self.edges() {
+            if !directed {
+                // each undirected edge is stored as a pair of directed edges, print only one of them.
+                let seen = undirected_seen
+                    .entry((from.min(to), from.max(to)))
+                    .or_default();
+                *seen += 1;
+                if seen.is_multiple_of(2) {
+                    continue;
+                }
+            }
+
+            let _ = match weight {
+                1 => writeln!(dot, "  {from} {arrow} {to};"),
+                _ => writeln!(dot, "  {from} {arrow} {to} [label=\"{weight}\"];"),
+            };
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn builds_graphs() {
+        let mut graph = Graph::directed([("a", "b"), ("a", "c")]);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.id(&"x"), None);
+        assert_eq!(graph.label(1), &"b");
+        assert_eq!(graph.neighbours(0), [(1, 1), (2, 1)]);
+        assert!(graph.neighbours(1).is_empty());
+
+        let d = graph.add_node("d");
+        assert_eq!(graph.add_node("a"), 0);
+        graph.add_undirected_edge(d, 0, 5);
+        assert_eq!(
+            graph.edges().collect::<Vec<_>>(),
+            [(0, 1, 1), (0, 2, 1), (0, 3, 5), (3, 0, 5)]
+        );
+    }
+
+    #[test]
+    fn contracts_corridors() {
+        // a square with a tail: a - b - c - d - a, d - e - f.
+        let graph = Graph::undirected([
+            ('a', 'b'),
+            ('b', 'c'),
+            ('c', 'd'),
+            ('d', 'a'),
+            ('d', 'e'),
+            ('e', 'f'),
+        ]);
+
+        let junctions = graph.contract_corridors(|_| false);
+        assert_eq!(junctions.len(), 2);
+        let (d, f) = (junctions.id(&'d').unwrap(), junctions.id(&'f').unwrap());
+        assert_eq!(junctions.neighbours(f), [(d, 2)]);
+        // the loop around the square returns to d and is dropped.
+        assert_eq!(junctions.neighbours(d), [(f, 2)]);
+
+        let kept = graph.contract_corridors(|id| *graph.label(id) == 'b');
+        let (b, d) = (kept.id(&'b').unwrap(), kept.id(&'d').unwrap());
+        let mut from_b = kept.neighbours(b).to_vec();
+        from_b.sort();
+        assert_eq!(from_b, [(d, 2), (d, 2)]);
+    }
+
+    #[test]
+    fn finds_components() {
+        let graph = Graph::directed([(1, 2), (3, 2), (4, 5), (6, 6)]);
+        let components: Vec<Vec<i32>> = graph
+            .components()
+            .iter()
+            .map(|c| {
+                let mut labels: Vec<_> = c.iter().map(|id| *graph.label(*id)).collect();
+                labels.sort();
+                labels
+            })
+            .collect();
+        assert_eq!(components, [vec![1, 2, 3], vec![4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = Graph::directed([
+            ("shirt", "tie"),
+            ("tie", "jacket"),
+            ("pants", "shoes"),
+            ("pants", "jacket"),
+        ]);
+        let order = graph.topological_sort().unwrap();
+        let position = |label| {
+            order
+                .iter()
+                .position(|id| *graph.label(*id) == label)
+                .unwrap()
+        };
+        for (from, to, _) in graph.edges() {
+            assert!(position(*graph.label(from)) < position(*graph.label(to)));
+        }
+
+        assert_eq!(
+            Graph::directed([(1, 2), (2, 3), (3, 1)]).topological_sort(),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_min_cuts() {
+        // two triangles joined by a single edge.
+        let graph = Graph::undirected([(1, 2), (2, 3), (3, 1), (4, 5), (5, 6), (6, 4), (3, 4)]);
+        let (weight, side) = graph.min_cut().unwrap();
+        let mut labels: Vec<_> = side.iter().map(|id| *graph.label(*id)).collect();
+        labels.sort();
+        assert_eq!(weight, 1);
+        assert!(labels == [1, 2, 3] || labels == [4, 5, 6], "{labels:?}");
+
+        let mut weighted = Graph::new();
+        let (a, b, c) = (
+            weighted.add_node('a'),
+            weighted.add_node('b'),
+            weighted.add_node('c'),
+        );
+        weighted.add_undirected_edge(a, b, 5);
+        weighted.add_undirected_edge(b, c, 2);
+        weighted.add_undirected_edge(a, c, 4);
+        assert_eq!(weighted.min_cut(), Some((6, vec![c])));
+
+        assert_eq!(Graph::undirected([(1, 2), (3, 4)]).min_cut().unwrap().0, 0);
+        assert_eq!(Graph::<u8>::new().min_cut(), None);
+    }
+
+    #[test]
+    fn exports_dot() {
+        let mut graph = Graph::undirected([("a", "b")]);
+        let c = graph.add_node("c\"");
+        graph.add_undirected_edge(1, c, 3);
+        assert_eq!(
+            graph.to_dot(false),
+            "graph {\n  0 [label=\"a\"];\n  1 [label=\"b\"];\n  2 [label=\"c\\\"\"];\n  0 -- 1;\n  1 -- 2 [label=\"3\"];\n}\n"
+        );
+        assert!(Graph::directed([(1, 2)]).to_dot(true).contains("0 -> 1;"));
+    }
+}
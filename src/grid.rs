@@ -0,0 +1,475 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// Offsets of the four orthogonal neighbours: up, right, down, left.
+const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the eight neighbours including diagonals, clockwise from the top left.
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Positions are `(x, y)` pairs: `x` is the column, counted from the left, and `y` the row, counted
+/// from the top, as the grid is read from the puzzle input.
+///
+/// ```
+/// # use advent_of_code::Grid;
+/// let grid: Grid<char> = "#..\n.#.".parse().unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid[(1, 1)], '#');
+/// assert_eq!(grid.get_signed((-1, 0)), None);
+/// assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` x `height` cells with the same value.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, returns `None` if they differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a grid with one cell per character. Errors point at the offending character or row.
+    pub fn try_parse(input: &str) -> Result<Self, ParseError>
+    where
+        T: TryFrom<char>,
+        T::Error: Into<ParseError>,
+    {
+        Self::parse_with(input, |c| T::try_from(c).map_err(Into::into))
+    }
+
+    fn parse_with(
+        input: &str,
+        cell: impl Fn(char) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut rows: Vec<Vec<T>> = vec![];
+
+        for line in input.lines() {
+            let row = line
+                .char_indices()
+                .map(|(idx, c)| cell(c).map_err(|e| e.within(input, &line[idx..])))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(width) = rows
+                .first()
+                .map(Vec::len)
+                .filter(|width| *width != row.len())
+            {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected a row of width {width}, found {}", row.len()),
+                ));
+            }
+
+            rows.push(row);
+        }
+
+        if rows.first().is_none_or(Vec::is_empty) {
+            return Err(ParseError::at(
+                input,
+                input,
+                "expected a grid, found empty input",
+            ));
+        }
+
+        Ok(Self::from_rows(rows).expect("rows have the same width"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether a signed position lies within the grid.
+    pub fn contains(&self, (x, y): (isize, isize)) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Returns the cell at a signed position, `None` if it lies outside of the grid.
+    pub fn get_signed(&self, pos: (isize, isize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self[(pos.0 as usize, pos.1 as usize)])
+    }
+
+    /// Returns the cell at a signed position of the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// Moves `pos` by `(dx, dy)`, returns `None` if that leaves the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.index_of(pos).map(|_| pos)
+    }
+
+    /// Returns the up to four orthogonal neighbours of a position within the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_4
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// Returns the up to eight neighbours of a position within the grid, including diagonals.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_8
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// Returns all positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |idx| (idx % width, idx / width))
+    }
+
+    /// Returns all cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Returns the position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// Returns the position of the first cell matching `predicate`, row by row.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the positions of all cells equal to `value`.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Creates a grid of the same size from the cells of this one.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Creates a grid of size `width` x `height` whose cell at `(x, y)` is the cell at `source(x, y)` of this one.
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: (0..width * height)
+                .map(|idx| self[source(idx % width, idx / width)].clone())
+                .collect(),
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearranged(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearranged(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        match self.index_of(pos) {
+            Some(idx) => &self.cells[idx],
+            None => panic!(
+                "position {pos:?} out of bounds of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        match self.index_of(pos) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!(
+                "position {pos:?} out of bounds of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Parses a grid with one cell per character, see [`Grid::try_parse`] for cells that can fail to parse.
+impl<T: From<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| Ok(T::from(c)))
+    }
+}
+
+/// Prints the cells row by row, without separators.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use crate::ParseError;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cell {
+        Wall,
+        Open,
+    }
+
+    impl TryFrom<char> for Cell {
+        type Error = ParseError;
+
+        fn try_from(c: char) -> Result<Self, Self::Error> {
+            match c {
+                '#' => Ok(Self::Wall),
+                '.' => Ok(Self::Open),
+                _ => Err(ParseError::new(format!("unknown cell '{c}'"))),
+            }
+        }
+    }
+
+    const EXAMPLE: &str = "ab\ncd\nef";
+
+    #[test]
+    fn parses_grids() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.to_string(), EXAMPLE);
+
+        let grid = Grid::<Cell>::try_parse("#.\n.#").unwrap();
+        assert_eq!(grid.find(&Cell::Wall), Some((0, 0)));
+        assert_eq!(
+            grid.find_all(&Cell::Wall).collect::<Vec<_>>(),
+            [(0, 0), (1, 1)]
+        );
+
+        let err = Grid::<Cell>::try_parse("#.\n.x").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert_eq!(err.message(), "unknown cell 'x'");
+
+        let err = "ab\nc".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert_eq!(err.message(), "expected a row of width 2, found 1");
+
+        assert!("".parse::<Grid<char>>().is_err());
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn indexes_cells() {
+        let mut grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get_signed((1, -1)), None);
+        assert_eq!(grid.get_signed((1, 1)), Some(&'d'));
+        assert_eq!(grid.get_wrapping((-1, 3)), &'b');
+        assert_eq!(grid.get_wrapping((4, -4)), &'e');
+
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (2, 0)), None);
+
+        grid[(0, 1)] = 'x';
+        *grid.get_mut((1, 1)).unwrap() = 'y';
+        assert_eq!(grid.row(1), ['x', 'y']);
+        assert_eq!(grid.position(|c| *c == 'y'), Some((1, 1)));
+    }
+
+    #[test]
+    fn iterates_neighbours_and_views() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b'], ['c', 'd'], ['e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ace", "bdf"]
+        );
+        assert_eq!(grid.iter().nth(3), Some(((1, 1), &'d')));
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "AB\nCD\nEF"
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid: Grid<char> = EXAMPLE.parse().unwrap();
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace");
+        assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe");
+        assert_eq!(grid.flip_vertical().to_string(), "ef\ncd\nab");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
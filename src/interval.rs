@@ -0,0 +1,508 @@
+use std::ops::{Range, RangeInclusive};
+
+use num::PrimInt;
+
+/// A half-open interval of integers, `start..end`. It is empty if `end <= start`.
+///
+/// ```
+/// # use advent_of_code::Interval;
+/// let a = Interval::new(0, 10);
+/// assert_eq!(a.intersection(&Interval::new(5, 15)), Some(Interval::new(5, 10)));
+/// assert_eq!(a.difference(&Interval::new(3, 5)).collect::<Vec<_>>(), [Interval::new(0, 3), Interval::new(5, 10)]);
+/// assert_eq!(a.split_at(4), (Some(Interval::new(0, 4)), Some(Interval::new(4, 10))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval `start..=end`.
+    pub fn inclusive(start: T, end: T) -> Self {
+        Self::new(start, end + T::one())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns the number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+
+    /// Returns the values in both intervals, `None` if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end)).non_empty()
+    }
+
+    /// Returns the up to two parts of this interval that are not in `other`, in ascending order.
+    pub fn difference(&self, other: &Self) -> impl Iterator<Item = Self> {
+        let parts = if other.is_empty() {
+            [Some(*self), None]
+        } else {
+            [
+                Self::new(self.start, self.end.min(other.start)),
+                Self::new(self.start.max(other.end), self.end),
+            ]
+            .map(Self::non_empty)
+        };
+
+        parts.into_iter().flatten().filter(|part| !part.is_empty())
+    }
+
+    /// Splits the interval into the values below `at` and the ones from `at` on.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        (
+            Self::new(self.start, at.min(self.end)).non_empty(),
+            Self::new(at.max(self.start), self.end).non_empty(),
+        )
+    }
+
+    /// Moves the interval up by `offset`.
+    pub fn shift(&self, offset: T) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+
+    /// Moves the interval so that `from` ends up at `to`, e.g. to apply a mapping from one range onto another.
+    /// For unsigned values, `from` must not be larger than `start`.
+    pub fn translate(&self, from: T, to: T) -> Self {
+        Self::new(self.start - from + to, self.end - from + to)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T: PrimInt> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        Self::inclusive(start, end)
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch.
+///
+/// ```
+/// # use advent_of_code::{Interval, IntervalSet};
+/// let mut set: IntervalSet<i64> = [Interval::new(0, 5), Interval::new(10, 15)].into_iter().collect();
+/// set.insert(Interval::new(5, 7));
+/// set.remove(Interval::new(12, 13));
+/// assert_eq!(set.iter().copied().collect::<Vec<_>>(), [Interval::new(0, 7), Interval::new(10, 12), Interval::new(13, 15)]);
+/// assert_eq!(set.len(), 11);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Adds all values of `interval`, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let from = self.intervals.partition_point(|i| i.end < interval.start);
+        let to = self.intervals.partition_point(|i| i.start <= interval.end);
+
+        let mut merged = interval;
+        if from < to {
+            merged.start = merged.start.min(self.intervals[from].start);
+            merged.end = merged.end.max(self.intervals[to - 1].end);
+        }
+
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    /// Removes all values of `interval`.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let from = self.intervals.partition_point(|i| i.end <= interval.start);
+        let to = self.intervals.partition_point(|i| i.start < interval.end);
+
+        let rest: Vec<_> = self.intervals[from..to]
+            .iter()
+            .flat_map(|i| i.difference(&interval))
+            .collect();
+        self.intervals.splice(from..to, rest);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter().copied());
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (0, 0);
+        let mut intervals = vec![];
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let (left, right) = (self.intervals[a], other.intervals[b]);
+            intervals.extend(left.intersection(&right));
+
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in other.iter() {
+            result.remove(*interval);
+        }
+        result
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(idx).is_some_and(|i| i.start <= value)
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |sum, i| sum + i.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the smallest value in the set.
+    pub fn first(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// Returns the largest value in the set.
+    pub fn last(&self) -> Option<T> {
+        self.intervals.last().map(|i| i.end - T::one())
+    }
+
+    /// Moves all values up by `offset`.
+    pub fn shift(&self, offset: T) -> Self {
+        Self {
+            intervals: self.intervals.iter().map(|i| i.shift(offset)).collect(),
+        }
+    }
+
+    /// Returns the intervals of the set in ascending order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T: PrimInt> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// An axis-aligned box in `N` dimensions, one interval per axis. It is empty if any axis is.
+///
+/// ```
+/// # use advent_of_code::{Interval, IntervalBox};
+/// let cube = IntervalBox::new([Interval::new(0, 3); 3]);
+/// let corner = IntervalBox::new([Interval::new(2, 5); 3]);
+/// assert_eq!(cube.volume(), 27);
+/// assert_eq!(cube.intersection(&corner).map(|i| i.volume()), Some(1));
+/// assert_eq!(cube.difference(&corner).iter().map(|b| b.volume()).sum::<i32>(), 26);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const N: usize> {
+    pub axes: [Interval<T>; N],
+}
+
+impl<T: PrimInt, const N: usize> IntervalBox<T, N> {
+    pub fn new(axes: [Interval<T>; N]) -> Self {
+        Self { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// Returns the number of points in the box.
+    pub fn volume(&self) -> T {
+        self.axes
+            .iter()
+            .fold(T::one(), |volume, axis| volume * axis.len())
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(axis, value)| axis.contains(value))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Self { axes })
+    }
+
+    /// Splits the box along `axis` into the part below `at` and the part from `at` on.
+    pub fn split_at(&self, axis: usize, at: T) -> (Option<Self>, Option<Self>) {
+        let (below, above) = self.axes[axis].split_at(at);
+        let with_axis = |interval: Interval<T>| {
+            let mut axes = self.axes;
+            axes[axis] = interval;
+            Self { axes }
+        };
+
+        (below.map(with_axis), above.map(with_axis))
+    }
+
+    /// Returns disjoint boxes covering the points of this box that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        let mut parts = vec![];
+        let mut rest = *self;
+
+        // cut off the slabs before and after the overlap axis by axis, what remains is the overlap.
+        for (axis, interval) in overlap.axes.iter().enumerate() {
+            let (below, from) = rest.split_at(axis, interval.start);
+            let (inside, above) = from
+                .expect("the overlap is not empty")
+                .split_at(axis, interval.end);
+            parts.extend(below);
+            parts.extend(above);
+            rest = inside.expect("the overlap is not empty");
+        }
+
+        parts
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalBox, IntervalSet};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Sets are checked against bitmasks of the values `0..64`.
+    const UNIVERSE: u32 = 64;
+    const CASES: usize = 500;
+
+    fn random_interval(rng: &mut StdRng) -> Interval<u32> {
+        let start = rng.gen_range(0..UNIVERSE);
+        Interval::new(start, rng.gen_range(start..=UNIVERSE))
+    }
+
+    fn random_set(rng: &mut StdRng) -> IntervalSet<u32> {
+        (0..rng.gen_range(0..6))
+            .map(|_| random_interval(rng))
+            .collect()
+    }
+
+    fn mask(set: &IntervalSet<u32>) -> u64 {
+        (0..UNIVERSE)
+            .filter(|v| set.contains(*v))
+            .fold(0, |mask, v| mask | 1 << v)
+    }
+
+    fn assert_normalized(set: &IntervalSet<u32>) {
+        assert!(
+            set.iter().all(|i| !i.is_empty()),
+            "{set:?} contains empty intervals"
+        );
+        assert!(
+            set.iter()
+                .zip(set.iter().skip(1))
+                .all(|(a, b)| a.end < b.start),
+            "{set:?} is not sorted or has touching intervals"
+        );
+        assert_eq!(set.len(), mask(set).count_ones(), "{set:?}");
+    }
+
+    #[test]
+    fn computes_intervals() {
+        let a = Interval::new(2, 8);
+        assert_eq!(a.len(), 6);
+        assert!(a.contains(2) && !a.contains(8));
+        assert_eq!(Interval::new(5, 1).len(), 0);
+        assert_eq!(Interval::from(1..=3), Interval::new(1, 4));
+        assert_eq!(std::ops::Range::from(a), 2..8);
+
+        assert_eq!(a.intersection(&Interval::new(8, 10)), None);
+        assert!(a.overlaps(&Interval::new(7, 10)));
+        assert_eq!(
+            a.difference(&Interval::new(0, 5)).collect::<Vec<_>>(),
+            [Interval::new(5, 8)]
+        );
+        assert_eq!(a.difference(&Interval::new(0, 10)).count(), 0);
+        assert_eq!(a.difference(&Interval::new(6, 3)).collect::<Vec<_>>(), [a]);
+        assert_eq!(a.split_at(0), (None, Some(a)));
+        assert_eq!(a.split_at(8), (Some(a), None));
+
+        assert_eq!(a.shift(-2), Interval::new(0, 6));
+        assert_eq!(
+            Interval::new(98u64, 100).translate(98, 50),
+            Interval::new(50, 52)
+        );
+    }
+
+    #[test]
+    fn interval_difference_partitions() {
+        let mut rng = StdRng::seed_from_u64(2023);
+
+        for _ in 0..CASES {
+            let (a, b) = (random_interval(&mut rng), random_interval(&mut rng));
+            let parts: Vec<_> = a.difference(&b).chain(a.intersection(&b)).collect();
+
+            for v in 0..=UNIVERSE {
+                let count = parts.iter().filter(|part| part.contains(v)).count();
+                assert_eq!(count, usize::from(a.contains(v)), "{a:?} - {b:?} at {v}");
+            }
+
+            let at = rng.gen_range(0..=UNIVERSE);
+            let (below, above) = a.split_at(at);
+            assert_eq!(
+                below.map_or(0, |i| i.len()) + above.map_or(0, |i| i.len()),
+                a.len()
+            );
+            assert!(below.is_none_or(|i| i.end <= at) && above.is_none_or(|i| i.start >= at));
+        }
+    }
+
+    #[test]
+    fn set_algebra_matches_bitmasks() {
+        let mut rng = StdRng::seed_from_u64(2023);
+
+        for _ in 0..CASES {
+            let (a, b) = (random_set(&mut rng), random_set(&mut rng));
+            let (ma, mb) = (mask(&a), mask(&b));
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+
+            for set in [&a, &b, &union, &intersection, &difference] {
+                assert_normalized(set);
+            }
+
+            assert_eq!(mask(&union), ma | mb, "{a:?} | {b:?}");
+            assert_eq!(mask(&intersection), ma & mb, "{a:?} & {b:?}");
+            assert_eq!(mask(&difference), ma & !mb, "{a:?} - {b:?}");
+
+            assert_eq!(union, b.union(&a));
+            assert_eq!(intersection, b.intersection(&a));
+            assert_eq!(difference.union(&intersection), a);
+            assert!(difference.intersection(&b).is_empty());
+
+            let v = rng.gen_range(0..UNIVERSE);
+            assert_eq!(a.contains(v), ma & 1 << v != 0);
+            assert_eq!(a.first(), (ma != 0).then(|| ma.trailing_zeros()));
+            assert_eq!(a.last(), (ma != 0).then(|| 63 - ma.leading_zeros()));
+        }
+    }
+
+    #[test]
+    fn shifts_sets() {
+        let set: IntervalSet<i64> = [Interval::new(0, 2), Interval::new(5, 6)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            set.shift(-1).iter().copied().collect::<Vec<_>>(),
+            [Interval::new(-1, 1), Interval::new(4, 5)]
+        );
+        assert_eq!(IntervalSet::<i64>::new().len(), 0);
+    }
+
+    #[test]
+    fn box_difference_partitions() {
+        let mut rng = StdRng::seed_from_u64(2023);
+        let random_box = |rng: &mut StdRng| {
+            IntervalBox::new([(); 3].map(|_| {
+                let start = rng.gen_range(0..8);
+                Interval::new(start, rng.gen_range(start..=8))
+            }))
+        };
+
+        for _ in 0..CASES {
+            let (a, b) = (random_box(&mut rng), random_box(&mut rng));
+            let parts = a.difference(&b);
+            let overlap = a.intersection(&b);
+
+            assert_eq!(
+                parts.iter().map(|p| p.volume()).sum::<u32>() + overlap.map_or(0, |o| o.volume()),
+                a.volume(),
+                "{a:?} - {b:?}"
+            );
+
+            for point in (0..8 * 8 * 8).map(|i| [i % 8, i / 8 % 8, i / 64]) {
+                let count = parts.iter().filter(|part| part.contains(point)).count();
+                assert_eq!(
+                    count,
+                    usize::from(a.contains(point) && !b.contains(point)),
+                    "{a:?} - {b:?} at {point:?}"
+                );
+            }
+        }
+    }
+}
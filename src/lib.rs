@@ -1,4 +1,24 @@
+mod cycle;
 mod day;
+mod geom;
+mod graph;
+mod grid;
+mod interval;
+mod math;
+mod ocr;
+mod parse;
+mod polygon;
+mod sequence;
 pub mod template;
 
+pub use cycle::*;
 pub use day::*;
+pub use geom::*;
+pub use graph::*;
+pub use grid::*;
+pub use interval::*;
+pub use math::*;
+pub use ocr::*;
+pub use parse::*;
+pub use polygon::*;
+pub use sequence::*;
@@ -1,56 +1,161 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, generate, leaderboard, login, logout, next, read, scaffold, scale, solve,
+    stars, status, verify, whoami,
+};
+use advent_of_code::template::unlock::SystemClock;
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::time::Duration;
+    use std::{env, process};
 
+    use advent_of_code::template::config;
     use advent_of_code::Day;
 
+    /// Input size used by `generate` when `--size` is not given.
+    const DEFAULT_GENERATE_SIZE: usize = 100;
+
+    /// Seed used by `scale` when `--seed` is not given, so runs are comparable.
+    const DEFAULT_SCALE_SEED: u64 = 2023;
+
+    /// Seconds a single run of `scale` may take before no larger sizes are tried.
+    const DEFAULT_SCALE_BUDGET: f64 = 2.0;
+
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: Option<u64>,
+            out: Option<String>,
+        },
+        Leaderboard {
+            id: Option<u64>,
+            file: Option<String>,
+            day: Option<Day>,
+        },
+        Login {
+            token: Option<String>,
+            check: bool,
+        },
+        Logout,
+        Next,
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
         },
+        Scale {
+            day: Day,
+            part: u8,
+            sizes: Option<Vec<usize>>,
+            seed: u64,
+            budget: Duration,
+        },
+        Stars,
+        Status {
+            json: bool,
+            run_tests: bool,
+        },
         Solve {
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            variant: Option<String>,
+            compare_variants: bool,
         },
         All {
             release: bool,
             time: bool,
+            report: Option<String>,
+            junit: Option<String>,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            junit: Option<String>,
+            timeout: Option<Duration>,
         },
+        Whoami,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
+
+        parse_config(&mut args)?;
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                report: args.opt_value_from_str("--report")?,
+                junit: args.opt_value_from_str("--junit")?,
+                timeout: parse_timeout(&mut args)?,
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("generate") => AppArguments::Generate {
+                day: args.free_from_str()?,
+                size: args
+                    .opt_value_from_str("--size")?
+                    .unwrap_or(DEFAULT_GENERATE_SIZE),
+                seed: args.opt_value_from_str("--seed")?,
+                out: args.opt_value_from_str("--out")?,
+            },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                file: args.opt_value_from_str("--file")?,
+                day: args.opt_value_from_str("--day")?,
+                id: args.opt_free_from_str()?,
+            },
+            Some("login") => AppArguments::Login {
+                check: !args.contains("--no-check"),
+                token: args.opt_free_from_str()?,
+            },
+            Some("logout") => AppArguments::Logout,
+            Some("next") => AppArguments::Next,
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
+            Some("scale") => AppArguments::Scale {
+                day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?.unwrap_or(1),
+                sizes: args.opt_value_from_fn("--sizes", parse_sizes)?,
+                seed: args
+                    .opt_value_from_str("--seed")?
+                    .unwrap_or(DEFAULT_SCALE_SEED),
+                budget: Duration::try_from_secs_f64(
+                    args.opt_value_from_str("--budget")?
+                        .unwrap_or(DEFAULT_SCALE_BUDGET),
+                )?,
+            },
+            Some("stars") => AppArguments::Stars,
+            Some("status") => AppArguments::Status {
+                json: args.contains("--json"),
+                run_tests: !args.contains("--skip-tests"),
+            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                variant: args.opt_value_from_str("--variant")?,
+                compare_variants: args.contains("--compare-variants"),
+            },
+            Some("verify") => AppArguments::Verify {
+                junit: args.opt_value_from_str("--junit")?,
+                timeout: parse_timeout(&mut args)?,
             },
+            Some("whoami") => AppArguments::Whoami,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -68,6 +173,37 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Loads `aoc.toml`, or the file passed with `--config`, and applies the global overrides like `--year`.
+    fn parse_config(args: &mut pico_args::Arguments) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(path) = args.opt_value_from_str::<_, String>("--config")? {
+            // solution bins read the config themselves, point them to the same file.
+            env::set_var("AOC_CONFIG", path);
+        }
+
+        if let Some(year) = args.opt_value_from_str::<_, u16>("--year")? {
+            // same for the year, `config::load` picks it up from the environment.
+            env::set_var("AOC_YEAR", year.to_string());
+        }
+
+        config::init(config::load()?);
+        Ok(())
+    }
+
+    /// Reads `--timeout <seconds>`, falling back to the timeout in `aoc.toml`.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        match timeout {
+            Some(timeout) => Ok(Some(Duration::try_from_secs_f64(timeout)?)),
+            None => Ok(config::get().bench.timeout),
+        }
+    }
+
+    fn parse_sizes(s: &str) -> Result<Vec<usize>, std::num::ParseIntError> {
+        s.split(',').map(|size| size.trim().parse()).collect()
+    }
 }
 
 fn main() {
@@ -77,16 +213,52 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                report,
+                junit,
+                timeout,
+            } => all::handle(&all::Options {
+                is_release: release,
+                is_timed: time,
+                report,
+                junit,
+                verify: false,
+                timeout,
+            }),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Generate {
+                day,
+                size,
+                seed,
+                out,
+            } => generate::handle(day, size, seed, out),
+            AppArguments::Leaderboard { id, file, day } => leaderboard::handle(id, file, day),
+            AppArguments::Login { token, check } => login::handle(token, check),
+            AppArguments::Logout => logout::handle(),
+            AppArguments::Next => next::handle(&SystemClock),
+            AppArguments::Read { day, part } => read::handle(day, part),
             AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Scale {
+                day,
+                part,
+                sizes,
+                seed,
+                budget,
+            } => scale::handle(day, part, sizes, seed, budget),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Status { json, run_tests } => status::handle(json, run_tests),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                variant,
+                compare_variants,
+            } => solve::handle(day, release, time, submit, variant, compare_variants),
+            AppArguments::Verify { junit, timeout } => verify::handle(junit, timeout),
+            AppArguments::Whoami => whoami::handle(),
         },
     };
 }
@@ -0,0 +1,332 @@
+use num::{Integer, PrimInt, Signed};
+
+/// Returns `(gcd, x, y)` with `a * x + b * y == gcd` and a non-negative `gcd`.
+///
+/// ```
+/// # use advent_of_code::extended_gcd;
+/// let (gcd, x, y) = extended_gcd(240, 46);
+/// assert_eq!(gcd, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns the least common multiple of all values, or `None` if it does not fit in `T`. The lcm of no values is 1.
+///
+/// ```
+/// # use advent_of_code::lcm_all;
+/// assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+/// assert_eq!(lcm_all([u8::MAX, 2]), None);
+/// ```
+pub fn lcm_all<T: PrimInt + Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::one(), |lcm, value| {
+        if lcm.is_zero() || value.is_zero() {
+            return Some(T::zero());
+        }
+        (lcm / Integer::gcd(&lcm, &value)).checked_mul(&value)
+    })
+}
+
+/// Returns `base` to the power of `exp`, modulo `modulus`. The result lies in `0..modulus`, also for a negative
+/// `base`.
+///
+/// ```
+/// # use advent_of_code::mod_pow;
+/// assert_eq!(mod_pow(4, 13, 497), 445);
+/// assert_eq!(mod_pow(-2, 3, 5), 2);
+/// ```
+pub fn mod_pow<T: PrimInt>(base: T, exp: u64, modulus: T) -> T {
+    let modulus = to_modulus(modulus);
+    let mut base = to_i128(base).rem_euclid(modulus as i128) as u128;
+    let (mut exp, mut result) = (exp, 1 % modulus);
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp >>= 1;
+    }
+
+    from_u128(result)
+}
+
+/// Returns the `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, or `None` if `a` and `modulus` are not coprime.
+///
+/// ```
+/// # use advent_of_code::mod_inverse;
+/// assert_eq!(mod_inverse(3, 11), Some(4));
+/// assert_eq!(mod_inverse(6, 9), None);
+/// ```
+pub fn mod_inverse<T: PrimInt>(a: T, modulus: T) -> Option<T> {
+    let modulus = to_modulus(modulus) as i128;
+    let (gcd, x, _) = extended_gcd(to_i128(a).rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| from_u128(x.rem_euclid(modulus) as u128))
+}
+
+/// Solves a system of congruences `x ≡ remainder (mod modulus)`, given as `(remainder, modulus)` pairs. The
+/// moduli do not need to be coprime.
+///
+/// Returns the solution as `(x, lcm)`, with `x` in `0..lcm` and every `x + k * lcm` solving the system too. Returns
+/// `None` if the congruences contradict each other or the lcm does not fit in `T`.
+///
+/// ```
+/// # use advent_of_code::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), None);
+/// ```
+pub fn crt<T: PrimInt>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (x, lcm) = congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let m2 = to_modulus(m2) as i128;
+            let r2 = to_i128(r2).rem_euclid(m2);
+
+            // x = r1 + m1 * t, where m1 * t ≡ r2 - r1 (mod m2) has a solution only if the gcd divides the difference.
+            let (gcd, inverse, _) = extended_gcd(m1, m2);
+            if (r2 - r1) % gcd != 0 {
+                return None;
+            }
+
+            let lcm = m1.checked_mul(m2 / gcd)?;
+            let step = (m2 / gcd) as u128;
+            let diff = ((r2 - r1) / gcd).rem_euclid(step as i128) as u128;
+            let t = mul_mod(diff, inverse.rem_euclid(step as i128) as u128, step) as i128;
+
+            Some((r1 + m1 * t, lcm))
+        })?;
+
+    Some((T::from(x)?, T::from(lcm)?))
+}
+
+/// Something that happens periodically: first at step `offset`, then every `period` steps.
+///
+/// Puzzles that combine several of these often only work because the offsets line up, e.g. each of them
+/// equals its period. Combining them explicitly states that assumption.
+///
+/// ```
+/// # use advent_of_code::Periodic;
+/// let a = Periodic::new(2, 3);
+/// let b = Periodic::new(3, 4);
+/// assert_eq!(a.intersection(b), Some(Periodic::new(11, 12)));
+/// assert_eq!(Periodic::intersect_all([Periodic::new(4, 4), Periodic::new(6, 6)]), Some(Periodic::new(12, 12)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Periodic<T> {
+    pub offset: T,
+    pub period: T,
+}
+
+impl<T: PrimInt> Periodic<T> {
+    pub fn new(offset: T, period: T) -> Self {
+        Self { offset, period }
+    }
+
+    /// Returns whether it happens at `step`.
+    pub fn contains(&self, step: T) -> bool {
+        step >= self.offset && ((step - self.offset) % self.period).is_zero()
+    }
+
+    /// Returns the `n`th step it happens at, counting from 0.
+    pub fn nth(&self, n: T) -> Option<T> {
+        n.checked_mul(&self.period)?.checked_add(&self.offset)
+    }
+
+    /// Returns when both happen at the same step, or `None` if they never do or the steps do not fit in `T`.
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        let (remainder, period) = crt([
+            (self.offset % self.period, self.period),
+            (other.offset % other.period, other.period),
+        ])?;
+
+        // the first common step cannot come before either of them starts.
+        let start = self.offset.max(other.offset);
+        let offset = if start <= remainder {
+            remainder
+        } else {
+            let behind = start - remainder;
+            let (periods, rest) = (behind / period, behind % period);
+            let periods = if rest.is_zero() {
+                periods
+            } else {
+                periods + T::one()
+            };
+            periods.checked_mul(&period)?.checked_add(&remainder)?
+        };
+
+        Some(Self { offset, period })
+    }
+
+    /// Returns when all of them happen at the same step. Combining none of them gives one that happens at
+    /// every step.
+    pub fn intersect_all(periodics: impl IntoIterator<Item = Self>) -> Option<Self> {
+        periodics
+            .into_iter()
+            .try_fold(Self::new(T::zero(), T::one()), Self::intersection)
+    }
+}
+
+fn to_i128<T: PrimInt>(value: T) -> i128 {
+    value.to_i128().expect("value must fit in an i128")
+}
+
+fn to_modulus<T: PrimInt>(modulus: T) -> u128 {
+    let modulus = to_i128(modulus);
+    assert!(modulus > 0, "modulus must be positive");
+    modulus as u128
+}
+
+fn from_u128<T: PrimInt>(value: u128) -> T {
+    T::from(value).expect("a value below the modulus fits in its type")
+}
+
+/// Multiplies `a` and `b`, both below `modulus`, without overflowing.
+fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    // both are below 2^127, so their sum fits.
+    let (mut a, mut b, mut result) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, lcm_all, mod_inverse, mod_pow, Periodic};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn computes_gcds() {
+        assert_eq!(extended_gcd(0, 0), (0, 1, 0));
+        assert_eq!(extended_gcd(0, -5).0, 5);
+        assert_eq!(extended_gcd(-12i64, 18).0, 6);
+
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..1000 {
+            let (a, b) = (rng.gen_range(-1000i64..1000), rng.gen_range(-1000..1000));
+            let (gcd, x, y) = extended_gcd(a, b);
+            assert_eq!(gcd, num::integer::gcd(a, b), "{a} {b}");
+            assert_eq!(a * x + b * y, gcd, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn computes_lcms() {
+        assert_eq!(lcm_all::<u32>([]), Some(1));
+        assert_eq!(lcm_all([0u32, 5]), Some(0));
+        assert_eq!(lcm_all([18_446_744_073_709_551_557u64, 3]), None);
+        assert_eq!(lcm_all([1u64 << 40, 1 << 63]), Some(1 << 63));
+        assert_eq!(
+            lcm_all([11_653u64, 19_783, 19_241, 16_531, 12_737, 14_363]),
+            Some(9_177_460_370_549)
+        );
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_pow(2u64, 64, 1_000_000_007), 582_344_008);
+        assert_eq!(mod_pow(i128::MAX - 1, 2, i128::MAX), 1);
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..1000 {
+            let modulus = rng.gen_range(1i64..200);
+            let (base, exp) = (rng.gen_range(-500..500), rng.gen_range(0..20));
+            let expected = (0..exp).fold(1 % modulus, |acc, _| (acc * base).rem_euclid(modulus));
+            assert_eq!(
+                mod_pow(base, exp, modulus),
+                expected,
+                "{base}^{exp} % {modulus}"
+            );
+
+            let inverse = (0..modulus).find(|x| (base * x).rem_euclid(modulus) == 1 % modulus);
+            assert_eq!(mod_inverse(base, modulus), inverse, "{base} % {modulus}");
+        }
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt::<i64>([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(5u8, 15), (3, 19)]), None);
+
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..1000 {
+            let congruences: Vec<(i64, i64)> = (0..rng.gen_range(1..4))
+                .map(|_| {
+                    let modulus = rng.gen_range(1..30);
+                    (rng.gen_range(0..modulus), modulus)
+                })
+                .collect();
+
+            let lcm = lcm_all(congruences.iter().map(|(_, m)| *m)).unwrap();
+            let solves = |x: i64| congruences.iter().all(|(r, m)| x % m == *r);
+            let expected = (0..lcm).find(|x| solves(*x)).map(|x| (x, lcm));
+            assert_eq!(
+                crt(congruences.iter().copied()),
+                expected,
+                "{congruences:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn combines_periodics() {
+        let a = Periodic::new(7u64, 5);
+        assert!(a.contains(12));
+        assert!(!a.contains(2));
+        assert_eq!(a.nth(2), Some(17));
+
+        // 2 satisfies both congruences, but neither has started yet.
+        assert_eq!(
+            Periodic::new(7, 5).intersection(Periodic::new(8, 3)),
+            Some(Periodic::new(17, 15))
+        );
+        assert_eq!(Periodic::new(1, 2).intersection(Periodic::new(0, 2)), None);
+        assert_eq!(Periodic::<u8>::intersect_all([]), Some(Periodic::new(0, 1)));
+
+        let mut rng = StdRng::seed_from_u64(2023);
+        for _ in 0..1000 {
+            let a = Periodic::new(rng.gen_range(0u64..30), rng.gen_range(1..12));
+            let b = Periodic::new(rng.gen_range(0..30), rng.gen_range(1..12));
+
+            let common: Vec<u64> = (0..1000)
+                .filter(|x| a.contains(*x) && b.contains(*x))
+                .take(2)
+                .collect();
+            let expected =
+                (!common.is_empty()).then(|| Periodic::new(common[0], common[1] - common[0]));
+            assert_eq!(a.intersection(b), expected, "{a:?} {b:?}");
+        }
+    }
+}
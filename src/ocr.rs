@@ -0,0 +1,173 @@
+/// Letters of the small font, 4 pixels wide (`I` and `Y` differ) and 6 high, used e.g. in 2016 day 8 and 2022 day 10.
+const FONT_6: [(char, [&str; 6]); 19] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('D', ["###.", "#..#", "#..#", "#..#", "#..#", "###."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the large font, 6 pixels wide and 10 high, used e.g. in 2018 day 10.
+const FONT_10: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Splits the pixels into glyphs at the columns that are not lit in any row.
+fn glyphs(rows: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let is_blank = |col: usize| rows.iter().all(|row| !row.get(col).copied().unwrap_or(false));
+
+    let mut glyphs = vec![];
+    let mut col = 0;
+
+    while col < width {
+        if is_blank(col) {
+            col += 1;
+            continue;
+        }
+
+        let end = (col..width).find(|&c| is_blank(c)).unwrap_or(width);
+        glyphs.push(
+            rows.iter()
+                .map(|row| (col..end).map(|c| row.get(c).copied().unwrap_or(false)).collect())
+                .collect(),
+        );
+        col = end;
+    }
+
+    glyphs
+}
+
+fn matches(glyph: &[Vec<bool>], letter: &[&str]) -> bool {
+    let letter = letter
+        .iter()
+        .map(|row| row.chars().map(is_lit).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // glyphs are cut at blank columns, so the font has to be as well.
+    glyphs(&letter).first().is_some_and(|letter| letter == glyph)
+}
+
+/// Recognizes text drawn in one of the letter fonts of Advent of Code, as printed by puzzles that ask
+/// to "read" a grid of pixels. Lit pixels are `#` or `█`, anything else is dark.
+/// Returns `None` if the drawing has an unexpected height or contains an unknown glyph.
+///
+/// ```
+/// # use advent_of_code::ocr;
+/// let art = [
+///     "#..#.###",
+///     "#..#..#.",
+///     "####..#.",
+///     "#..#..#.",
+///     "#..#..#.",
+///     "#..#.###",
+/// ]
+/// .join("\n");
+/// assert_eq!(ocr(&art).as_deref(), Some("HI"));
+/// ```
+pub fn ocr(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect::<Vec<_>>())
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+    let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    let rows = &rows[..height];
+
+    glyphs(rows)
+        .iter()
+        .map(|glyph| match height {
+            6 => FONT_6.iter().find(|(_, letter)| matches(glyph, letter)).map(|(c, _)| *c),
+            10 => FONT_10.iter().find(|(_, letter)| matches(glyph, letter)).map(|(c, _)| *c),
+            _ => None,
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ocr, FONT_10, FONT_6};
+
+    /// Draws text in a font, letters separated by `gap` blank columns.
+    fn draw<const N: usize>(font: &[(char, [&str; N])], text: &str, gap: usize) -> String {
+        (0..N)
+            .map(|row| {
+                text.chars()
+                    .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1[row])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_small_font() {
+        let art = [
+            "###..#..#.###..####.#..#.#..#.###..#..#.",
+            "#..#.#..#.#..#....#.#..#.#..#.#..#.#.#..",
+            "#..#.#..#.#..#...#..####.#..#.#..#.##...",
+            "###..#..#.###...#...#..#.#..#.###..#.#..",
+            "#.#..#..#.#.#..#....#..#.#..#.#.#..#.#..",
+            "#..#..##..#..#.####.#..#..##..#..#.#..#.",
+        ]
+        .join("\n");
+        assert_eq!(ocr(&art).as_deref(), Some("RURZHURK"));
+
+        let alphabet: String = FONT_6.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr(&draw(&FONT_6, &alphabet, 1)), Some(alphabet));
+    }
+
+    #[test]
+    fn reads_large_font() {
+        let alphabet: String = FONT_10.iter().map(|(c, _)| c).collect();
+        assert_eq!(ocr(&draw(&FONT_10, &alphabet, 2)), Some(alphabet));
+    }
+
+    #[test]
+    fn handles_other_pixels_and_padding() {
+        let art = draw(&FONT_6, "EF", 1).replace('#', "█").replace('.', " ");
+        assert_eq!(ocr(&format!("\n   \n{art}\n\n")).as_deref(), Some("EF"));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(ocr("#.#\n.#.\n#.#\n.#.\n#.#\n.#."), None);
+        assert_eq!(ocr("##\n##"), None);
+        assert_eq!(ocr("...\n..."), None);
+    }
+}
@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error raised while parsing puzzle input.
+///
+/// Carries the position (1-based line and column) of the offending input together with
+/// the line it occurred on, so failures can point at the exact spot that could not be parsed.
+///
+/// ```
+/// # use advent_of_code::ParseError;
+/// let input = "1 2\n3 x\n";
+/// let err = ParseError::at(input, &input[6..7], "expected a number");
+/// assert_eq!((err.line(), err.column()), (2, 3));
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    location: Option<Location>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    offset: usize,
+    line: usize,
+    column: usize,
+    snippet: String,
+}
+
+impl ParseError {
+    /// Creates a [`ParseError`] without position information.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+            source: None,
+        }
+    }
+
+    /// Creates a [`ParseError`] pointing at `fragment`, which must be a sub-slice of `input`.
+    ///
+    /// If `fragment` is not part of `input` (e.g. an owned, modified copy), the error falls back
+    /// to searching `input` for the fragment's text and to no position if that fails, too.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = offset_of(input, fragment).or_else(|| {
+            if fragment.is_empty() {
+                None
+            } else {
+                input.find(fragment)
+            }
+        });
+
+        Self {
+            message: message.into(),
+            location: offset.map(|offset| Location::from_offset(input, offset)),
+            source: None,
+        }
+    }
+
+    /// Re-anchors an error that was raised while parsing `fragment` on its own (e.g. a single line
+    /// handed to a [`FromStr`] implementation) to its position within the surrounding `input`.
+    ///
+    /// Errors without a position are placed at the start of `fragment`.
+    #[must_use]
+    pub fn within(mut self, input: &str, fragment: &str) -> Self {
+        if let Some(base) = offset_of(input, fragment) {
+            let offset = base + self.location.as_ref().map_or(0, |l| l.offset);
+            self.location = Some(Location::from_offset(input, offset));
+        }
+        self
+    }
+
+    /// Attaches the underlying error that caused this one.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// The error message, without position information.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The 1-based line of the offending input, or `0` if unknown.
+    pub fn line(&self) -> usize {
+        self.location.as_ref().map_or(0, |l| l.line)
+    }
+
+    /// The 1-based column (in characters) of the offending input, or `0` if unknown.
+    pub fn column(&self) -> usize {
+        self.location.as_ref().map_or(0, |l| l.column)
+    }
+
+    /// The input line the error occurred on, if known.
+    pub fn snippet(&self) -> Option<&str> {
+        self.location.as_ref().map(|l| l.snippet.as_str())
+    }
+}
+
+impl Location {
+    fn from_offset(input: &str, offset: usize) -> Self {
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            offset,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }
+    }
+}
+
+/// Returns the byte offset of `fragment` in `input` if `fragment` points into `input`.
+fn offset_of(input: &str, fragment: &str) -> Option<usize> {
+    let start = input.as_ptr() as usize;
+    let ptr = fragment.as_ptr() as usize;
+
+    if ptr < start || ptr + fragment.len() > start + input.len() {
+        return None;
+    }
+
+    let offset = ptr - start;
+    input.is_char_boundary(offset).then_some(offset)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            None => write!(f, "{}", self.message),
+            Some(location) => {
+                writeln!(
+                    f,
+                    "{} (line {}, column {})",
+                    self.message, location.line, location.column
+                )?;
+                writeln!(f, "  | {}", location.snippet)?;
+                write!(f, "  | {}^", " ".repeat(location.column - 1))
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|e| e.as_ref() as &(dyn Error + 'static))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses `fragment` (a sub-slice of `input`) with [`FromStr`], reporting failures at the fragment's position.
+///
+/// ```
+/// # use advent_of_code::parse_at;
+/// let input = "seeds: 79 x4";
+/// assert_eq!(parse_at::<u32>(input, &input[7..9]).unwrap(), 79);
+/// assert_eq!(parse_at::<u32>(input, &input[10..12]).unwrap_err().column(), 11);
+/// ```
+pub fn parse_at<T>(input: &str, fragment: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    fragment.parse().map_err(|e| {
+        ParseError::at(input, fragment, format!("could not parse {fragment:?}")).with_source(e)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_at, ParseError};
+    use std::error::Error;
+
+    #[test]
+    fn locates_fragment() {
+        let input = "abc\ndef\nghi";
+        let err = ParseError::at(input, &input[9..10], "bad");
+        assert_eq!(err.line(), 3);
+        assert_eq!(err.column(), 2);
+        assert_eq!(err.snippet(), Some("ghi"));
+    }
+
+    #[test]
+    fn locates_copied_fragment() {
+        let input = "abc\ndef\nghi";
+        let copy = "ef".to_string();
+        let err = ParseError::at(input, &copy, "bad");
+        assert_eq!((err.line(), err.column()), (2, 2));
+    }
+
+    #[test]
+    fn unknown_location() {
+        let err = ParseError::at("abc", "xyz", "bad");
+        assert_eq!((err.line(), err.column()), (0, 0));
+        assert_eq!(err.to_string(), "bad");
+    }
+
+    #[test]
+    fn relocates_line_errors() {
+        let input = "abc\ndef\nghi";
+        let line = input.lines().nth(1).unwrap();
+        let err = ParseError::at(line, &line[2..], "bad").within(input, line);
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert_eq!(err.snippet(), Some("def"));
+
+        let err = ParseError::new("bad").within(input, line);
+        assert_eq!((err.line(), err.column()), (2, 1));
+    }
+
+    #[test]
+    fn formats_snippet() {
+        let input = "1 2\n3 x\n";
+        let err = ParseError::at(input, &input[6..7], "expected a number");
+        assert_eq!(
+            err.to_string(),
+            "expected a number (line 2, column 3)\n  | 3 x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn keeps_source() {
+        let input = "12a";
+        let err = parse_at::<u32>(input, input).unwrap_err();
+        assert!(err.source().is_some());
+        assert_eq!(err.message(), "could not parse \"12a\"");
+    }
+}
@@ -0,0 +1,326 @@
+use crate::Direction;
+use num::{Integer, PrimInt, Signed};
+
+/// A closed polygon with integer vertices, given in order around its boundary. The last vertex connects back
+/// to the first one.
+///
+/// All computations are done in `i128` and checked, so `i64` coordinates never wrap around. `i128`
+/// coordinates work as long as the intermediate products fit, a computation that would overflow panics.
+///
+/// ```
+/// # use advent_of_code::{Direction, Polygon};
+/// let walk = [(Direction::Right, 4), (Direction::Down, 2), (Direction::Left, 4), (Direction::Up, 2)];
+/// let polygon = Polygon::from_walk((0, 0), walk);
+/// assert_eq!(polygon.double_area(), 16);
+/// assert_eq!(polygon.boundary_points(), 12);
+/// assert_eq!(polygon.interior_points(), Some(3));
+/// assert!(polygon.contains((4, 1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<T> {
+    vertices: Vec<(T, T)>,
+}
+
+impl<T: PrimInt + Signed> Polygon<T> {
+    /// Creates a polygon from its vertices, accepts tuples and [`Point`](crate::Point)s.
+    pub fn new<V: Into<(T, T)>>(vertices: impl IntoIterator<Item = V>) -> Self {
+        Self {
+            vertices: vertices.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Creates the polygon traced by walking the given number of steps in each direction, starting from `start`.
+    /// Like [`Point`](crate::Point), `y` grows downwards. A walk that returns to `start` does not repeat it
+    /// as the last vertex.
+    pub fn from_walk(start: (T, T), steps: impl IntoIterator<Item = (Direction, T)>) -> Self {
+        let mut vertices = vec![start];
+        let (mut x, mut y) = start;
+
+        for (direction, amount) in steps {
+            match direction {
+                Direction::Up => y = y - amount,
+                Direction::Right => x = x + amount,
+                Direction::Down => y = y + amount,
+                Direction::Left => x = x - amount,
+            }
+            vertices.push((x, y));
+        }
+
+        if vertices.len() > 1 && vertices.last() == Some(&start) {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[(T, T)] {
+        &self.vertices
+    }
+
+    /// Returns twice the signed area, which is positive if the vertices go clockwise with `y` growing downwards
+    /// (counterclockwise with `y` growing upwards). Twice the area of a lattice polygon is always an integer.
+    pub fn signed_double_area(&self) -> i128 {
+        self.edges().fold(0, |sum, ((x1, y1), (x2, y2))| {
+            let cross =
+                checked(checked(x1.checked_mul(y2)).checked_sub(checked(x2.checked_mul(y1))));
+            checked(sum.checked_add(cross))
+        })
+    }
+
+    /// Returns twice the area enclosed by the polygon.
+    pub fn double_area(&self) -> u128 {
+        self.signed_double_area().unsigned_abs()
+    }
+
+    /// Returns the number of lattice points on the boundary, the vertices included.
+    pub fn boundary_points(&self) -> u128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| {
+                let dx = checked(x2.checked_sub(x1)).unsigned_abs();
+                let dy = checked(y2.checked_sub(y1)).unsigned_abs();
+                dx.gcd(&dy)
+            })
+            .sum()
+    }
+
+    /// Returns the number of lattice points strictly inside the polygon, using Pick's theorem
+    /// `A = I + B / 2 - 1`. The polygon must not intersect itself, returns `None` if the boundary is too long for
+    /// the area, e.g. because an edge doubles back on the previous one.
+    pub fn interior_points(&self) -> Option<u128> {
+        let double_area = self.double_area();
+        if double_area == 0 {
+            return Some(0);
+        }
+
+        (double_area + 2)
+            .checked_sub(self.boundary_points())
+            .map(|x| x / 2)
+    }
+
+    /// Returns the number of lattice points inside or on the boundary of the polygon, or `None` like
+    /// [`interior_points`](Self::interior_points).
+    pub fn lattice_points(&self) -> Option<u128> {
+        Some(self.interior_points()? + self.boundary_points())
+    }
+
+    /// Returns whether `point` lies on one of the edges.
+    pub fn is_on_boundary(&self, point: (T, T)) -> bool {
+        let point = widen(point);
+        self.edges().any(|(a, b)| on_segment(a, b, point))
+    }
+
+    /// Returns whether `point` lies inside the polygon or on its boundary.
+    pub fn contains(&self, point: (T, T)) -> bool {
+        let p = widen(point);
+        let mut inside = false;
+
+        // cast a ray from `p` to the right and count the edges it crosses.
+        for (a, b) in self.edges() {
+            if on_segment(a, b, p) {
+                return true;
+            }
+
+            if (a.1 > p.1) != (b.1 > p.1) {
+                let side = cross(a, b, p);
+                inside ^= if b.1 > a.1 { side > 0 } else { side < 0 };
+            }
+        }
+
+        inside
+    }
+
+    fn edges(&self) -> impl Iterator<Item = ((i128, i128), (i128, i128))> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices
+            .iter()
+            .zip(next)
+            .map(|(&a, &b)| (widen(a), widen(b)))
+    }
+}
+
+fn widen<T: PrimInt>((x, y): (T, T)) -> (i128, i128) {
+    let to_i128 = |v: T| v.to_i128().expect("coordinates must fit in an i128");
+    (to_i128(x), to_i128(y))
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("polygon computation overflows an i128")
+}
+
+/// The cross product of `b - a` and `p - a`, positive if `p` lies to the left of `a -> b` with `y` growing upwards.
+fn cross(a: (i128, i128), b: (i128, i128), p: (i128, i128)) -> i128 {
+    let delta = |from: (i128, i128), to: (i128, i128)| {
+        (
+            checked(to.0.checked_sub(from.0)),
+            checked(to.1.checked_sub(from.1)),
+        )
+    };
+    let (ux, uy) = delta(a, b);
+    let (vx, vy) = delta(a, p);
+    checked(checked(ux.checked_mul(vy)).checked_sub(checked(uy.checked_mul(vx))))
+}
+
+fn on_segment(a: (i128, i128), b: (i128, i128), p: (i128, i128)) -> bool {
+    let within = |v: i128, from: i128, to: i128| from.min(to) <= v && v <= from.max(to);
+    within(p.0, a.0, b.0) && within(p.1, a.1, b.1) && cross(a, b, p) == 0
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Polygon;
+    use crate::{Direction, Point};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Counts lattice points by checking every point of the bounding box.
+    fn brute_force(polygon: &Polygon<i64>) -> (u128, u128) {
+        let (xs, ys): (Vec<i64>, Vec<i64>) = polygon.vertices().iter().copied().unzip();
+        let (mut boundary, mut inside) = (0, 0);
+
+        for x in *xs.iter().min().unwrap()..=*xs.iter().max().unwrap() {
+            for y in *ys.iter().min().unwrap()..=*ys.iter().max().unwrap() {
+                if polygon.is_on_boundary((x, y)) {
+                    boundary += 1;
+                } else if polygon.contains((x, y)) {
+                    inside += 1;
+                }
+            }
+        }
+
+        (boundary, inside)
+    }
+
+    #[test]
+    fn measures_polygons() {
+        // an L shape, going clockwise with y growing downwards.
+        let polygon = Polygon::<i64>::new([(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+        assert_eq!(polygon.signed_double_area(), 24);
+        assert_eq!(polygon.boundary_points(), 16);
+        assert_eq!(polygon.interior_points(), Some(5));
+        assert_eq!(polygon.lattice_points(), Some(21));
+
+        let reversed = Polygon::new(polygon.vertices().iter().rev().copied());
+        assert_eq!(reversed.signed_double_area(), -24);
+        assert_eq!(reversed.interior_points(), Some(5));
+
+        let triangle = Polygon::new([Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)]);
+        assert_eq!(triangle.double_area(), 12);
+        assert_eq!(triangle.boundary_points(), 8);
+        assert_eq!(triangle.interior_points(), Some(3));
+
+        assert_eq!(
+            Polygon::<i64>::new(Vec::<(i64, i64)>::new()).lattice_points(),
+            Some(0)
+        );
+        assert_eq!(
+            Polygon::<i64>::new([(1, 1), (3, 1)]).interior_points(),
+            Some(0)
+        );
+
+        // a square with a spike that goes out and back along the same line.
+        let spiked = Polygon::<i64>::from_walk(
+            (0, 0),
+            [
+                (Direction::Right, 2),
+                (Direction::Right, 5),
+                (Direction::Left, 5),
+                (Direction::Down, 2),
+                (Direction::Left, 2),
+                (Direction::Up, 2),
+            ],
+        );
+        assert_eq!(spiked.double_area(), 8);
+        assert_eq!(spiked.interior_points(), None);
+        assert_eq!(spiked.lattice_points(), None);
+    }
+
+    #[test]
+    fn builds_from_walks() {
+        let walk = [
+            (Direction::Right, 4),
+            (Direction::Down, 2),
+            (Direction::Left, 2),
+            (Direction::Down, 2),
+            (Direction::Left, 2),
+            (Direction::Up, 4),
+        ];
+        let polygon = Polygon::<i64>::from_walk((0, 0), walk);
+        assert_eq!(
+            polygon.vertices(),
+            [(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]
+        );
+
+        let open = Polygon::<i64>::from_walk((1, 1), [(Direction::Up, 3), (Direction::Left, 1)]);
+        assert_eq!(open.vertices(), [(1, 1), (1, -2), (0, -2)]);
+    }
+
+    #[test]
+    fn locates_points() {
+        let polygon = Polygon::<i64>::new([(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)]);
+
+        assert!(polygon.contains((1, 1)));
+        assert!(polygon.contains((1, 3)));
+        assert!(polygon.contains((3, 2)));
+        assert!(!polygon.contains((3, 3)));
+        assert!(!polygon.contains((5, 0)));
+        assert!(!polygon.contains((-1, 2)));
+
+        assert!(polygon.is_on_boundary((0, 0)));
+        assert!(polygon.is_on_boundary((3, 2)));
+        assert!(!polygon.is_on_boundary((1, 1)));
+    }
+
+    #[test]
+    fn handles_large_coordinates() {
+        let far = i64::MAX / 2;
+        let square = Polygon::new([(-far, -far), (far, -far), (far, far), (-far, far)]);
+        let side = 2 * far as u128;
+        assert_eq!(square.double_area(), 2 * side * side);
+        assert_eq!(square.boundary_points(), 4 * side);
+        assert_eq!(square.interior_points(), Some((side - 1) * (side - 1)));
+        assert!(square.contains((0, far)));
+        assert!(!square.contains((far, far + 1)));
+
+        let offset = i128::MAX / 4;
+        let triangle = Polygon::new([(offset, 0), (offset + 4, 0), (offset, 3)]);
+        assert_eq!(triangle.interior_points(), Some(3));
+        assert!(triangle.contains((offset + 1, 1)));
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = StdRng::seed_from_u64(2023);
+
+        for _ in 0..300 {
+            // random triangles are always simple, even if they are degenerate.
+            let mut vertex = || (rng.gen_range(-8..8), rng.gen_range(-8..8));
+            let polygon = Polygon::<i64>::new([vertex(), vertex(), vertex()]);
+
+            let (boundary, inside) = brute_force(&polygon);
+            if polygon.double_area() > 0 {
+                assert_eq!(polygon.boundary_points(), boundary, "{polygon:?}");
+            }
+            assert_eq!(polygon.interior_points(), Some(inside), "{polygon:?}");
+        }
+
+        for _ in 0..100 {
+            // a staircase that goes up and right, then returns along the bottom and left edges.
+            let mut walk = vec![];
+            let (mut width, mut height) = (0, 0);
+            for _ in 0..rng.gen_range(1..5) {
+                let (up, right) = (rng.gen_range(1..4), rng.gen_range(1..4));
+                walk.extend([(Direction::Up, up), (Direction::Right, right)]);
+                height += up;
+                width += right;
+            }
+            walk.extend([(Direction::Down, height), (Direction::Left, width)]);
+            let polygon = Polygon::<i64>::from_walk((0, 0), walk);
+
+            let (boundary, inside) = brute_force(&polygon);
+            assert_eq!(polygon.boundary_points(), boundary, "{polygon:?}");
+            assert_eq!(polygon.interior_points(), Some(inside), "{polygon:?}");
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use num::rational::Ratio;
+use num::{PrimInt, Signed, Zero};
+
+/// The finite difference table of a sequence: the values, their differences, the differences of those, and so on
+/// until the differences are all zero.
+///
+/// A sequence given by a polynomial of degree `d` has constant `d`th differences, so the table extends it in both
+/// directions with exact integer arithmetic.
+///
+/// ```
+/// # use advent_of_code::Differences;
+/// let squares = Differences::new(&[1, 4, 9, 16]);
+/// assert_eq!(squares.degree(), Some(2));
+/// assert_eq!(squares.extrapolate(1), 25);
+/// assert_eq!(squares.extrapolate_back(1), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Differences<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: PrimInt + Signed> Differences<T> {
+    pub fn new(values: &[T]) -> Self {
+        let mut rows = vec![values.to_vec()];
+
+        loop {
+            let differences: Vec<T> = rows
+                .last()
+                .unwrap()
+                .windows(2)
+                .map(|pair| checked(pair[1].checked_sub(&pair[0])))
+                .collect();
+
+            if differences.iter().all(|x| x.is_zero()) {
+                break;
+            }
+
+            rows.push(differences);
+        }
+
+        Self { rows }
+    }
+
+    /// Returns the values followed by each row of differences, the last row is constant.
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    /// Returns the degree of the polynomial that generates the sequence, or `None` if the sequence is too short to
+    /// tell: the last row of differences needs at least two equal values.
+    pub fn degree(&self) -> Option<usize> {
+        let last = self.rows.last().unwrap();
+        (last.len() >= 2).then(|| self.rows.len() - 1)
+    }
+
+    /// Returns the value `steps` places after the last one, assuming the last row of differences stays constant.
+    pub fn extrapolate(&self, steps: usize) -> T {
+        let mut lasts: Vec<T> = self
+            .rows
+            .iter()
+            .filter_map(|row| row.last().copied())
+            .collect();
+
+        for _ in 0..steps {
+            for i in (1..lasts.len()).rev() {
+                lasts[i - 1] = checked(lasts[i - 1].checked_add(&lasts[i]));
+            }
+        }
+
+        lasts.first().copied().unwrap_or_else(T::zero)
+    }
+
+    /// Returns the value `steps` places before the first one, assuming the last row of differences stays constant.
+    pub fn extrapolate_back(&self, steps: usize) -> T {
+        let mut firsts: Vec<T> = self
+            .rows
+            .iter()
+            .filter_map(|row| row.first().copied())
+            .collect();
+
+        for _ in 0..steps {
+            for i in (1..firsts.len()).rev() {
+                firsts[i - 1] = checked(firsts[i - 1].checked_sub(&firsts[i]));
+            }
+        }
+
+        firsts.first().copied().unwrap_or_else(T::zero)
+    }
+}
+
+fn checked<T>(value: Option<T>) -> T {
+    value.expect("sequence value overflows")
+}
+
+/// A polynomial with exact rational coefficients, lowest degree first.
+///
+/// ```
+/// # use advent_of_code::Polynomial;
+/// # use num::rational::Ratio;
+/// let polynomial = Polynomial::interpolate([(0, 1), (1, 2), (2, 5)]).unwrap();
+/// assert_eq!(polynomial.degree(), Some(2));
+/// assert_eq!(polynomial.at(10), Ratio::from_integer(101));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Ratio<i128>>,
+}
+
+impl Polynomial {
+    pub fn new(mut coefficients: Vec<Ratio<i128>>) -> Self {
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+
+        Self { coefficients }
+    }
+
+    /// Returns the polynomial of the lowest degree that goes through all `(x, y)` points, using Lagrange
+    /// interpolation. Returns `None` if two points share the same `x`.
+    pub fn interpolate<T: PrimInt>(points: impl IntoIterator<Item = (T, T)>) -> Option<Self> {
+        let points: Vec<(Ratio<i128>, Ratio<i128>)> = points
+            .into_iter()
+            .map(|(x, y)| (to_ratio(x), to_ratio(y)))
+            .collect();
+        let mut coefficients = vec![Ratio::zero(); points.len()];
+
+        for (i, &(xi, yi)) in points.iter().enumerate() {
+            // the basis polynomial is one at `xi` and zero at every other `x`.
+            let mut basis = vec![Ratio::from_integer(1)];
+            let mut denominator = Ratio::from_integer(1);
+
+            for (j, &(xj, _)) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                if xi == xj {
+                    return None;
+                }
+
+                basis = multiply_linear(&basis, xj);
+                denominator *= xi - xj;
+            }
+
+            for (coefficient, b) in coefficients.iter_mut().zip(basis) {
+                *coefficient += b * yi / denominator;
+            }
+        }
+
+        Some(Self::new(coefficients))
+    }
+
+    pub fn coefficients(&self) -> &[Ratio<i128>] {
+        &self.coefficients
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn eval(&self, x: Ratio<i128>) -> Ratio<i128> {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Ratio::zero(), |result, coefficient| {
+                result * x + coefficient
+            })
+    }
+
+    pub fn at<T: PrimInt>(&self, x: T) -> Ratio<i128> {
+        self.eval(to_ratio(x))
+    }
+}
+
+fn to_ratio<T: PrimInt>(value: T) -> Ratio<i128> {
+    Ratio::from_integer(value.to_i128().expect("value must fit in an i128"))
+}
+
+/// Multiplies a polynomial by `(x - root)`.
+fn multiply_linear(polynomial: &[Ratio<i128>], root: Ratio<i128>) -> Vec<Ratio<i128>> {
+    let mut product = vec![Ratio::zero(); polynomial.len() + 1];
+    for (i, &coefficient) in polynomial.iter().enumerate() {
+        product[i + 1] += coefficient;
+        product[i] -= coefficient * root;
+    }
+    product
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Differences, Polynomial};
+    use num::rational::Ratio;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn builds_difference_tables() {
+        let table = Differences::new(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(
+            table.rows(),
+            [
+                vec![10, 13, 16, 21, 30, 45],
+                vec![3, 3, 5, 9, 15],
+                vec![0, 2, 4, 6],
+                vec![2, 2, 2],
+            ]
+        );
+        assert_eq!(table.degree(), Some(3));
+
+        assert_eq!(Differences::new(&[0, 0, 0]).degree(), Some(0));
+        assert_eq!(Differences::new(&[1, 2, 4, 8]).degree(), None);
+        assert_eq!(Differences::<i32>::new(&[]).degree(), None);
+    }
+
+    #[test]
+    fn extrapolates_sequences() {
+        let table = Differences::new(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(table.extrapolate(0), 45);
+        assert_eq!(table.extrapolate(1), 68);
+        assert_eq!(table.extrapolate_back(0), 10);
+        assert_eq!(table.extrapolate_back(1), 5);
+
+        let line = Differences::new(&[0, 3, 6, 9, 12, 15]);
+        assert_eq!(line.extrapolate(1_000_000), 3_000_015);
+        assert_eq!(line.extrapolate_back(2), -6);
+
+        assert_eq!(Differences::new(&[7]).extrapolate(3), 7);
+        assert_eq!(Differences::<i32>::new(&[]).extrapolate(3), 0);
+    }
+
+    #[test]
+    fn interpolates_polynomials() {
+        let half = Polynomial::interpolate([(0, 0), (1, 1), (2, 3)]).unwrap();
+        assert_eq!(
+            half.coefficients(),
+            [Ratio::new(0, 1), Ratio::new(1, 2), Ratio::new(1, 2)]
+        );
+        assert_eq!(half.eval(Ratio::new(1, 2)), Ratio::new(3, 8));
+
+        assert_eq!(Polynomial::interpolate([(1, 2), (1, 3)]), None);
+        assert_eq!(Polynomial::interpolate::<i32>([]).unwrap().degree(), None);
+        assert_eq!(
+            Polynomial::interpolate([(3, 4), (5, 4)]).unwrap().degree(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn agrees_with_evaluation() {
+        let mut rng = StdRng::seed_from_u64(2023);
+
+        for _ in 0..200 {
+            let degree = rng.gen_range(0..5);
+            let coefficients: Vec<i64> = (0..=degree).map(|_| rng.gen_range(-20..20)).collect();
+            let eval = |x: i64| coefficients.iter().rev().fold(0, |y, c| y * x + c);
+
+            let start = rng.gen_range(-10..10);
+            let values: Vec<i64> = (start..start + 8).map(eval).collect();
+            let table = Differences::new(&values);
+
+            let expected_degree = coefficients.iter().rposition(|c| *c != 0).unwrap_or(0);
+            assert_eq!(table.degree(), Some(expected_degree), "{coefficients:?}");
+            assert_eq!(table.extrapolate(5), eval(start + 12), "{coefficients:?}");
+            assert_eq!(
+                table.extrapolate_back(5),
+                eval(start - 5),
+                "{coefficients:?}"
+            );
+
+            // the polynomial always goes through the points, it is only unique with enough of them.
+            let xs: Vec<i64> = (start..start + 8)
+                .step_by(rng.gen_range(1..3))
+                .skip(1)
+                .collect();
+            let polynomial = Polynomial::interpolate(xs.iter().map(|&x| (x, eval(x)))).unwrap();
+            for &x in &xs {
+                assert_eq!(
+                    polynomial.at(x),
+                    Ratio::from(eval(x) as i128),
+                    "{coefficients:?}"
+                );
+            }
+
+            if xs.len() > degree {
+                let expected = Polynomial::new(
+                    coefficients
+                        .iter()
+                        .map(|c| Ratio::from(*c as i128))
+                        .collect(),
+                );
+                assert_eq!(polynomial, expected, "{coefficients:?}");
+                assert_eq!(
+                    polynomial.at(100),
+                    Ratio::from(eval(100) as i128),
+                    "{coefficients:?}"
+                );
+            }
+        }
+    }
+}
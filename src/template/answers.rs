@@ -0,0 +1,84 @@
+/// Local record of accepted answers, kept in `data/answers/DD.txt` unless configured otherwise.
+///
+/// The file mirrors the output of the runner, one line per part:
+///
+/// ```text
+/// Part 1: 1234
+/// Part 2: 5678
+/// ```
+///
+/// Answers are recorded automatically when a submission is accepted, but the file can be edited by hand.
+use crate::template::config;
+use crate::Day;
+use std::{fs, io};
+
+fn get_answers_path(day: Day) -> String {
+    format!("{}/{day}.txt", config::get().paths.answers)
+}
+
+/// Returns the recorded answers of both parts of `day`.
+pub fn read(day: Day) -> [Option<String>; 2] {
+    fs::read_to_string(get_answers_path(day))
+        .map(|content| parse(&content))
+        .unwrap_or_default()
+}
+
+/// Records `answer` as the accepted answer of `part`, keeping the answer of the other part.
+pub fn record(day: Day, part: u8, answer: &str) -> io::Result<()> {
+    let mut answers = read(day);
+    let index = usize::from(part.clamp(1, 2) - 1);
+    answers[index] = Some(answer.to_string());
+
+    fs::create_dir_all(&config::get().paths.answers)?;
+    fs::write(get_answers_path(day), format(&answers))
+}
+
+fn parse(content: &str) -> [Option<String>; 2] {
+    let mut answers: [Option<String>; 2] = Default::default();
+
+    for line in content.lines() {
+        let Some((part, answer)) = line.split_once(':') else {
+            continue;
+        };
+
+        let index = match part.trim() {
+            "Part 1" => 0,
+            "Part 2" => 1,
+            _ => continue,
+        };
+
+        if !answer.trim().is_empty() {
+            answers[index] = Some(answer.trim().to_string());
+        }
+    }
+
+    answers
+}
+
+fn format(answers: &[Option<String>; 2]) -> String {
+    (1..)
+        .zip(answers)
+        .filter_map(|(part, answer)| Some(format!("Part {part}: {}\n", answer.as_ref()?)))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format, parse};
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse("Part 1: 1234\n\nPart 2:  abc \nnoise\n");
+        assert_eq!(answers, [Some("1234".into()), Some("abc".into())]);
+        assert_eq!(parse("Part 2: 7\nPart 1:"), [None, Some("7".into())]);
+    }
+
+    #[test]
+    fn formats_answers() {
+        let answers = [None, Some("42".into())];
+        assert_eq!(format(&answers), "Part 2: 42\n");
+        assert_eq!(parse(&format(&answers)), answers);
+    }
+}
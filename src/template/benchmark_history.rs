@@ -0,0 +1,446 @@
+/// Module that keeps a history of benchmark runs and renders it as a chart.
+///
+/// Every `cargo all --release --time` run adds one entry per day to `paths.benchmarks` of `aoc.toml`, keyed by the
+/// current commit. Running the benchmarks again on the same commit replaces its entries.
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use crate::template::config;
+use crate::template::readme_benchmarks::Timings;
+use crate::template::markup::escape;
+use crate::template::runner::parse_nanos;
+use crate::Day;
+
+/// Returns the file the history is recorded in, `data/benchmarks.csv` by default.
+pub fn history_path() -> String {
+    config::get().paths.benchmarks.clone()
+}
+
+/// Returns the file the chart is rendered to, `.assets/benchmarks.svg` by default.
+pub fn chart_path() -> String {
+    Path::new(&config::get().paths.assets)
+        .join("benchmarks.svg")
+        .to_string_lossy()
+        .into_owned()
+}
+
+const HEADER: &str = "commit,timestamp,day,part_1,part_2";
+
+/// Number of runs shown in the trend column of the readme.
+const TREND_LENGTH: usize = 8;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The timings of one day in one benchmark run, in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub commit: String,
+    pub timestamp: u64,
+    pub day: Day,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+impl Entry {
+    pub fn total(&self) -> Option<f64> {
+        match (self.part_1, self.part_2) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0_f64) + b.unwrap_or(0_f64)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct History {
+    pub entries: Vec<Entry>,
+}
+
+impl History {
+    /// Loads the history from disk. A missing file is an empty history.
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(history_path()) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = history_path();
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_csv())
+    }
+
+    /// Parses the CSV representation of a history, skipping malformed lines.
+    pub fn parse(content: &str) -> Self {
+        let entries = content
+            .lines()
+            .filter(|line| *line != HEADER)
+            .filter_map(|line| {
+                let [commit, timestamp, day, part_1, part_2] = line.split(',').collect::<Vec<_>>()[..] else {
+                    return None;
+                };
+
+                Some(Entry {
+                    commit: commit.to_string(),
+                    timestamp: timestamp.parse().ok()?,
+                    day: day.parse().ok()?,
+                    part_1: part_1.parse().ok(),
+                    part_2: part_2.parse().ok(),
+                })
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn to_csv(&self) -> String {
+        let format_nanos = |nanos: Option<f64>| nanos.map(|n| n.to_string()).unwrap_or_default();
+
+        let mut csv = format!("{HEADER}\n");
+        for entry in &self.entries {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                entry.commit,
+                entry.timestamp,
+                entry.day.into_inner(),
+                format_nanos(entry.part_1),
+                format_nanos(entry.part_2)
+            );
+        }
+        csv
+    }
+
+    /// Adds a run to the history, replacing an earlier run of the same commit.
+    pub fn record(&mut self, commit: &str, timestamp: u64, timings: &[Timings]) {
+        self.entries.retain(|entry| entry.commit != commit);
+        self.entries.extend(timings.iter().map(|timing| Entry {
+            commit: commit.to_string(),
+            timestamp,
+            day: timing.day,
+            part_1: timing.part_1.as_deref().and_then(parse_nanos),
+            part_2: timing.part_2.as_deref().and_then(parse_nanos),
+        }));
+    }
+
+    /// Returns all commits in the order they were first benchmarked.
+    pub fn commits(&self) -> Vec<&str> {
+        let mut commits: Vec<&str> = vec![];
+        for entry in &self.entries {
+            if !commits.contains(&entry.commit.as_str()) {
+                commits.push(&entry.commit);
+            }
+        }
+        commits
+    }
+
+    /// Returns the total time of `day` for every commit, [`None`] where it was not benchmarked.
+    pub fn series(&self, day: Day) -> Vec<Option<f64>> {
+        self.commits()
+            .into_iter()
+            .map(|commit| {
+                self.entries
+                    .iter()
+                    .find(|entry| entry.commit == commit && entry.day == day)
+                    .and_then(Entry::total)
+            })
+            .collect()
+    }
+
+    /// Returns the total time of all days for every commit.
+    pub fn totals(&self) -> Vec<f64> {
+        self.commits()
+            .into_iter()
+            .map(|commit| {
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.commit == commit)
+                    .filter_map(Entry::total)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Renders the recent history of `day` as a sparkline followed by the change against the previous run.
+    pub fn trend(&self, day: Day) -> Option<String> {
+        let values: Vec<f64> = self.series(day).into_iter().flatten().collect();
+        let recent = &values[values.len().saturating_sub(TREND_LENGTH)..];
+
+        let (&last, rest) = recent.split_last()?;
+        let Some(&previous) = rest.last() else {
+            return Some(sparkline(recent));
+        };
+
+        let change = (last / previous - 1_f64) * 100_f64;
+        let change = if change.abs() < 1_f64 {
+            "±0%".to_string()
+        } else {
+            format!("{change:+.0}%")
+        };
+
+        Some(format!("{} {change}", sparkline(recent)))
+    }
+}
+
+fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max <= min {
+                return SPARKS[SPARKS.len() / 2];
+            }
+            let ratio = (value - min) / (max - min);
+            SPARKS[(ratio * (SPARKS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+/// Identifies the current state of the repository, e.g. `4ad3d54` or `4ad3d54-dirty`.
+pub fn current_commit() -> String {
+    Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 420.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 110.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+
+fn day_color(day: Day) -> String {
+    format!("hsl({}, 65%, 45%)", (u32::from(day.into_inner()) * 137) % 360)
+}
+
+fn format_decade(exponent: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit = (exponent / 3).clamp(0, 3);
+    let value = 10_f64.powi(exponent - unit * 3);
+    format!("{value}{}", units[unit as usize])
+}
+
+/// Renders the time per day and the total time over all benchmarked commits as an SVG line chart.
+/// Times are plotted on a logarithmic scale, since days differ by orders of magnitude.
+pub fn render_svg(history: &History) -> String {
+    let commits = history.commits();
+    let days: Vec<Day> = crate::all_days()
+        .filter(|day| history.entries.iter().any(|entry| entry.day == *day))
+        .collect();
+    let totals = history.totals();
+
+    let values = history.entries.iter().filter_map(Entry::total).chain(totals.iter().copied());
+    let (min, max) = values
+        .filter(|value| *value > 0_f64)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| (min.min(v), max.max(v)));
+    let (low, high) = if min.is_finite() {
+        (min.log10().floor() as i32, (max.log10().ceil() as i32).max(min.log10().floor() as i32 + 1))
+    } else {
+        (3, 6)
+    };
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let x = |idx: usize| {
+        if commits.len() < 2 {
+            MARGIN_LEFT + plot_width / 2.0
+        } else {
+            MARGIN_LEFT + plot_width * idx as f64 / (commits.len() - 1) as f64
+        }
+    };
+    let y = |nanos: f64| {
+        let ratio = (nanos.max(1_f64).log10() - f64::from(low)) / f64::from(high - low);
+        MARGIN_TOP + plot_height * (1.0 - ratio.clamp(0.0, 1.0))
+    };
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    svg.push('\n');
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="18" font-size="13" font-weight="bold">Benchmark history</text>"#
+    );
+
+    // horizontal grid with one line per decade.
+    for exponent in low..=high {
+        let gy = y(10_f64.powi(exponent));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{gy:.1}" x2="{:.1}" y2="{gy:.1}" stroke="#ddd"/><text x="{:.1}" y="{:.1}" text-anchor="end" fill="#555">{}</text>"##,
+            WIDTH - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            gy + 4.0,
+            format_decade(exponent)
+        );
+    }
+
+    // commit labels, thinned out so they do not overlap.
+    let step = commits.len().div_ceil(12).max(1);
+    for (idx, commit) in commits.iter().enumerate().step_by(step) {
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{:.1}" text-anchor="middle" fill="#555">{}</text>"##,
+            x(idx),
+            HEIGHT - MARGIN_BOTTOM + 18.0,
+            escape(commit)
+        );
+    }
+
+    let mut plot = |series: &[Option<f64>], color: &str, width: f64| {
+        let points: Vec<(f64, f64)> = series
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, value)| Some((x(idx), y((*value)?))))
+            .collect();
+
+        let path = points.iter().map(|(px, py)| format!("{px:.1},{py:.1}")).collect::<Vec<_>>();
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="{width}"/>"#,
+            path.join(" ")
+        );
+        for (px, py) in points {
+            let _ = writeln!(svg, r#"<circle cx="{px:.1}" cy="{py:.1}" r="{}" fill="{color}"/>"#, width + 1.0);
+        }
+    };
+
+    for day in &days {
+        plot(&history.series(*day), &day_color(*day), 1.5);
+    }
+    plot(&totals.iter().map(|total| Some(*total)).collect::<Vec<_>>(), "black", 3.0);
+
+    // legend
+    let legend_x = WIDTH - MARGIN_RIGHT + 16.0;
+    let labels = days
+        .iter()
+        .map(|day| (format!("Day {day}"), day_color(*day)))
+        .chain(std::iter::once(("Total".to_string(), "black".to_string())));
+    for (idx, (label, color)) in labels.enumerate() {
+        let ly = MARGIN_TOP + idx as f64 * 14.0;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{legend_x}" y="{:.1}" width="10" height="10" fill="{color}"/><text x="{:.1}" y="{:.1}">{label}</text>"#,
+            ly,
+            legend_x + 16.0,
+            ly + 9.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Records a run in the history and renders the chart, returns the updated history.
+pub fn update(timings: &[Timings]) -> io::Result<History> {
+    let mut history = History::load()?;
+    history.record(&current_commit(), now(), timings);
+    history.save()?;
+
+    fs::create_dir_all(&config::get().paths.assets)?;
+    fs::write(chart_path(), render_svg(&history))?;
+
+    Ok(history)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_svg, sparkline, History};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+
+    fn timings(part_1: &str, part_2: &str) -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                part_1: Some(part_1.into()),
+                part_2: Some(part_2.into()),
+                total_nanos: 0.0,
+            },
+            Timings {
+                day: day!(2),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                total_nanos: 0.0,
+            },
+        ]
+    }
+
+    fn get_mock_history() -> History {
+        let mut history = History::default();
+        history.record("aaa", 1, &timings("10ms", "20ms"));
+        history.record("bbb", 2, &timings("5ms", "10ms"));
+        history.record("ccc", 3, &timings("5ms", "10ms"));
+        history
+    }
+
+    #[test]
+    fn roundtrips_csv() {
+        let history = get_mock_history();
+        let csv = history.to_csv();
+        assert!(csv.starts_with("commit,timestamp,day,part_1,part_2\naaa,1,1,10000000,20000000\n"));
+        assert!(csv.contains("aaa,1,2,1000000,\n"));
+        assert_eq!(History::parse(&csv), history);
+    }
+
+    #[test]
+    fn replaces_runs_of_same_commit() {
+        let mut history = get_mock_history();
+        history.record("bbb", 4, &timings("1ms", "1ms"));
+        assert_eq!(history.commits(), vec!["aaa", "ccc", "bbb"]);
+        assert_eq!(history.series(day!(1)), vec![Some(3e7), Some(1.5e7), Some(2e6)]);
+        assert_eq!(history.totals(), vec![3.1e7, 1.6e7, 3e6]);
+    }
+
+    #[test]
+    fn renders_trend() {
+        let history = get_mock_history();
+        assert_eq!(history.trend(day!(1)).as_deref(), Some("█▁▁ ±0%"));
+        assert_eq!(history.trend(day!(2)).as_deref(), Some("▅▅▅ ±0%"));
+        assert_eq!(history.trend(day!(3)), None);
+
+        let mut history = History::default();
+        history.record("aaa", 1, &timings("10ms", "20ms"));
+        assert_eq!(history.trend(day!(1)).as_deref(), Some("▅"));
+        history.record("bbb", 2, &timings("15ms", "30ms"));
+        assert_eq!(history.trend(day!(1)).as_deref(), Some("▁█ +50%"));
+
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0, 8.0]), "▁▂▃█");
+    }
+
+    #[test]
+    fn renders_chart() {
+        let svg = render_svg(&get_mock_history());
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        // one line per day plus the total.
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains(">Day 01<") && svg.contains(">Total<") && svg.contains(">ccc<"));
+
+        let empty = render_svg(&History::default());
+        assert_eq!(empty.matches("<polyline").count(), 1);
+    }
+}
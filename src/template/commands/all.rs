@@ -1,13 +1,39 @@
-use std::io;
+use std::fmt::Display;
+use std::time::Duration;
+use std::{fs, io};
 
 use crate::template::{
+    answers, config, html_report, junit,
     readme_benchmarks::{self, Timings},
+    runner::Stats,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Options for running all solutions, shared by `all` and `verify`.
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Path to write an HTML report to.
+    pub report: Option<String>,
+    /// Path to write JUnit XML to.
+    pub junit: Option<String>,
+    /// Compare the answers with the ones recorded in `data/answers`.
+    pub verify: bool,
+    /// Kills a solution that runs for longer than this.
+    pub timeout: Option<Duration>,
+}
+
+pub fn handle(options: &Options) {
+    run(options);
+}
+
+/// Runs all solutions and writes the requested reports, returns what was learned about every day.
+pub fn run(options: &Options) -> Vec<DayRun> {
     let mut timings: Vec<Timings> = vec![];
+    let mut runs: Vec<DayRun> = vec![];
+    let mut summary = Summary::default();
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -17,22 +43,64 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-        if output.is_empty() {
+        let Some(run) = child_commands::run_solution(day, options).unwrap() else {
             println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            summary.unsolved += 2;
+            runs.push(DayRun::unsolved(day));
+            return;
+        };
+
+        if run.timed_out {
+            println!("Timed out after {:?}.", options.timeout.unwrap_or_default());
+        } else if !run.success {
+            println!("Crashed while running the solution.");
         }
+
+        let mut day_run = child_commands::parse_day_run(&run, day);
+
+        for (part, expected) in day_run.parts.iter_mut().zip(answers::read(day)) {
+            if run.timed_out && part.status == PartStatus::Missing {
+                part.status = PartStatus::TimedOut;
+                part.nanos = options.timeout.map(|timeout| timeout.as_nanos() as f64);
+            }
+            if options.verify {
+                part.expected = expected;
+            }
+            summary.add(part.status);
+        }
+
+        runs.push(day_run);
+
+        let val = child_commands::parse_exec_time(&run.output, day);
+        timings.push(val);
     });
 
-    if is_timed {
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {summary}");
+
+    if options.verify {
+        print_verification(&runs);
+    }
+
+    if let Some(path) = &options.report {
+        match fs::write(path, html_report::render(&runs, &summary, path)) {
+            Ok(()) => println!("Wrote report to \"{path}\"."),
+            Err(e) => eprintln!("Failed to write report to \"{path}\": {e}"),
+        }
+    }
+
+    if let Some(path) = &options.junit {
+        match fs::write(path, junit::render(&runs, options.verify)) {
+            Ok(()) => println!("Wrote JUnit XML to \"{path}\"."),
+            Err(e) => eprintln!("Failed to write JUnit XML to \"{path}\": {e}"),
+        }
+    }
+
+    if options.is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        if options.is_release && config::get().readme.update_benchmarks {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -41,6 +109,153 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    runs
+}
+
+fn print_verification(runs: &[DayRun]) {
+    let (mut correct, mut wrong, mut unrecorded) = (0, 0, 0);
+
+    for run in runs {
+        for (part, result) in (1..).zip(&run.parts) {
+            match result.verdict(true) {
+                Verdict::Passed => correct += 1,
+                Verdict::WrongAnswer { expected } => {
+                    wrong += 1;
+                    println!(
+                        "✖ Day {} part {part}: expected {ANSI_BOLD}{expected}{ANSI_RESET}, got {ANSI_BOLD}{}{ANSI_RESET}",
+                        run.day,
+                        result.answer.as_deref().unwrap_or_default()
+                    );
+                }
+                Verdict::Skipped(reason) if reason == NO_RECORDED_ANSWER => unrecorded += 1,
+                Verdict::Errored(_) | Verdict::Skipped(_) => {}
+            }
+        }
+    }
+
+    println!("{ANSI_BOLD}Verified:{ANSI_RESET} {correct} correct, {wrong} wrong, {unrecorded} without a recorded answer");
+}
+
+/// The state of a solution part, as reported by the solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    Failed,
+    Panicked,
+    /// The part did not report anything, e.g. because the binary did not compile or crashed.
+    Missing,
+    /// The solution bin was killed before the part reported anything.
+    TimedOut,
+}
+
+const NO_RECORDED_ANSWER: &str = "no recorded answer";
+
+/// How a part fares as a test case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Passed,
+    /// The answer differs from the recorded one.
+    WrongAnswer { expected: String },
+    /// The part did not produce an answer because of an error, a panic or a timeout.
+    Errored(&'static str),
+    /// There is nothing to check, e.g. because the part has not been solved yet.
+    Skipped(&'static str),
+}
+
+/// What `all` learned about one part from the output of a solution bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartRun {
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// The (mean) execution time in nanoseconds.
+    pub nanos: Option<f64>,
+    /// Only reported for benchmarked parts when `all` runs with `--report`.
+    pub stats: Option<Stats>,
+    /// The recorded answer, only read when verifying.
+    pub expected: Option<String>,
+}
+
+impl PartRun {
+    /// Judges the part. When `verify` is set, a solved part passes only if it matches the recorded answer.
+    pub fn verdict(&self, verify: bool) -> Verdict {
+        match self.status {
+            PartStatus::Solved => match (&self.expected, &self.answer) {
+                (Some(expected), Some(answer)) if expected != answer => Verdict::WrongAnswer {
+                    expected: expected.clone(),
+                },
+                (None, _) if verify => Verdict::Skipped(NO_RECORDED_ANSWER),
+                _ => Verdict::Passed,
+            },
+            PartStatus::Unsolved => Verdict::Skipped("not solved"),
+            PartStatus::Failed => Verdict::Errored("error"),
+            PartStatus::Panicked => Verdict::Errored("panicked"),
+            PartStatus::Missing => Verdict::Errored("no output"),
+            PartStatus::TimedOut => Verdict::Errored("timed out"),
+        }
+    }
+}
+
+/// What `all` learned about one day from the output of its solution bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayRun {
+    pub day: Day,
+    pub parts: [PartRun; 2],
+    /// Peak memory of the solution bin in KiB, only reported when `all` runs with `--report`.
+    pub memory_kib: Option<u64>,
+    /// What the solution bin printed to stderr, e.g. errors and panic messages.
+    pub stderr: Vec<String>,
+}
+
+impl DayRun {
+    /// A day that has not been scaffolded yet.
+    pub fn unsolved(day: Day) -> Self {
+        let part = PartRun {
+            status: PartStatus::Unsolved,
+            answer: None,
+            nanos: None,
+            stats: None,
+            expected: None,
+        };
+
+        Self {
+            day,
+            parts: [part.clone(), part],
+            memory_kib: None,
+            stderr: vec![],
+        }
+    }
+}
+
+/// Counts the outcome of every part run by `all`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Summary {
+    pub solved: usize,
+    pub unsolved: usize,
+    pub failed: usize,
+    pub panicked: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, status: PartStatus) {
+        match status {
+            PartStatus::Solved => self.solved += 1,
+            PartStatus::Unsolved => self.unsolved += 1,
+            PartStatus::Failed | PartStatus::Missing | PartStatus::TimedOut => self.failed += 1,
+            PartStatus::Panicked => self.panicked += 1,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} solved, {} failed, {} panicked, {} not solved",
+            self.solved, self.failed, self.panicked, self.unsolved
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -63,38 +278,65 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
+pub mod child_commands {
+    use super::{get_path_for_bin, DayRun, Error, Options, PartRun, PartStatus};
+    use crate::template::runner::{parse_nanos, Stats};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// The captured output of a solution bin.
+    pub struct SolutionRun {
+        pub output: Vec<String>,
+        pub errors: Vec<String>,
+        pub success: bool,
+        pub timed_out: bool,
+    }
+
+    /// Run the solution bin for a given day, returns [`None`] if the day has not been scaffolded yet.
+    pub fn run_solution(day: Day, options: &Options) -> Result<Option<SolutionRun>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(None);
         }
 
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
+        if options.timeout.is_some() {
+            // build upfront, so compilation does not count towards the timeout.
+            let _ = Command::new("cargo")
+                .arg("build")
+                .args(&args[1..])
+                .status();
+        }
+
+        let with_stats = options.report.is_some();
+
+        if options.is_timed || with_stats {
             args.push("--");
+        }
+
+        if options.is_timed {
+            // mirror `--time` flag to child invocations.
             args.push("--time");
         }
 
+        if with_stats {
+            args.push("--stats");
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing the lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -105,24 +347,164 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stderr_thread = thread::spawn(move || {
+            stderr
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| eprintln!("{line}"))
+                .collect::<Vec<_>>()
+        });
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| println!("{line}"))
+                .collect::<Vec<_>>()
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let timer = Instant::now();
+        let mut timed_out = false;
+
+        let status = loop {
+            if let Some(status) = cmd.try_wait()? {
+                break status;
+            }
+
+            if options.timeout.is_some_and(|timeout| timer.elapsed() > timeout) {
+                let _ = cmd.kill();
+                timed_out = true;
+                break cmd.wait()?;
+            }
+
+            thread::sleep(Duration::from_millis(5));
+        };
+
+        let output = stdout_thread.join().unwrap();
+        let errors = stderr_thread.join().unwrap();
+
+        Ok(Some(SolutionRun {
+            output,
+            errors,
+            success: status.success(),
+            timed_out,
+        }))
+    }
+
+    /// Determines the status of both parts from the output of a solution bin.
+    pub fn parse_statuses(output: &[String]) -> [PartStatus; 2] {
+        let mut statuses = [PartStatus::Missing; 2];
+
+        for line in output.iter().map(|line| strip_ansi(line)) {
+            let Some((part, rest)) = line.split_once(':') else {
+                continue;
+            };
+
+            let index = match part.trim() {
+                "Part 1" => 0,
+                "Part 2" => 1,
+                _ => continue,
+            };
+
+            let rest = rest.trim();
+
+            statuses[index] = if rest.starts_with("✖ panicked") {
+                PartStatus::Panicked
+            } else if rest.starts_with("✖ error") {
+                PartStatus::Failed
+            } else if rest.starts_with('✖') {
+                PartStatus::Unsolved
+            } else {
+                PartStatus::Solved
+            };
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        statuses
+    }
 
-        Ok(output)
+    /// Removes ANSI escape sequences like `\x1b[1m` and intermediate results overwritten with `\r` from a line.
+    fn strip_ansi(line: &str) -> String {
+        let line = line.rsplit('\r').next().unwrap_or(line);
+        let mut stripped = String::with_capacity(line.len());
+        let mut chars = line.chars();
+
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            } else {
+                stripped.push(c);
+            }
+        }
+
+        stripped
+    }
+
+    /// Collects the answers, timings and statistics of both parts from the output of a solution bin.
+    pub fn parse_day_run(run: &SolutionRun, day: Day) -> DayRun {
+        let statuses = parse_statuses(&run.output);
+        let lines: Vec<String> = run.output.iter().map(|line| strip_ansi(line)).collect();
+
+        let parts = [1, 2].map(|part| {
+            let status = statuses[part as usize - 1];
+            let prefix = format!("Part {part}: ");
+            let line = lines.iter().position(|line| line.starts_with(&prefix));
+
+            let (answer, nanos) = line.map_or((None, None), |idx| {
+                let rest = lines[idx][prefix.len()..].trim_end();
+                let (value, time) = match rest.rsplit_once(" (") {
+                    Some((value, time)) if time.ends_with(')') => (value, Some(time)),
+                    _ => (rest, None),
+                };
+
+                let nanos = time.and_then(|time| parse_nanos(time.split(['@', ')']).next()?));
+
+                let answer = if status != PartStatus::Solved {
+                    None
+                } else if value.trim() == "▼" {
+                    // multi-line answers are printed below the result line, followed by the letters they
+                    // show if they are drawn in pixels. Those are what gets submitted and recorded.
+                    let letters_prefix = format!("Part {part} letters: ");
+                    let letters = lines.iter().find_map(|line| line.strip_prefix(&letters_prefix));
+                    let answer = lines[idx + 1..]
+                        .iter()
+                        .take_while(|line| !line.starts_with("Part ") && !line.starts_with("Peak memory:"))
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+                    Some(letters.map_or_else(|| answer.join("\n").trim_end().to_string(), |l| l.trim().to_string()))
+                } else {
+                    Some(value.trim().to_string())
+                };
+
+                (answer, nanos)
+            });
+
+            let stats = lines
+                .iter()
+                .filter_map(|line| Stats::parse(line))
+                .find_map(|(p, stats)| (p == part).then_some(stats));
+
+            PartRun {
+                status,
+                answer,
+                nanos: stats.map_or(nanos, |stats| Some(stats.mean.as_nanos() as f64)),
+                stats,
+                expected: None,
+            }
+        });
+
+        let memory_kib = lines.iter().find_map(|line| {
+            line.strip_prefix("Peak memory: ")?
+                .strip_suffix(" KiB")?
+                .parse()
+                .ok()
+        });
+
+        DayRun {
+            day,
+            parts,
+            memory_kib,
+            stderr: run.errors.iter().map(|line| strip_ansi(line)).collect(),
+        }
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -161,27 +543,17 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -202,7 +574,9 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_day_run, parse_exec_time, parse_statuses, SolutionRun};
+        use crate::template::commands::all::{PartStatus, Verdict};
+        use crate::template::commands::verify::has_failures;
 
         use crate::day;
 
@@ -250,5 +624,105 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_statuses() {
+            let res = parse_statuses(&[
+                "Part 1: ✖\rPart 1: ✖ panicked    ".into(),
+                "Part 2: \x1b[1m10\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 2: \x1b[1m10\x1b[0m (74.13ms @ 99999 samples)".into(),
+            ]);
+            assert_eq!(res, [PartStatus::Panicked, PartStatus::Solved]);
+
+            let res = parse_statuses(&[
+                "Part 1: ✖\rPart 1: ✖ error       ".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(res, [PartStatus::Failed, PartStatus::Unsolved]);
+
+            let res = parse_statuses(&["error: could not compile".into()]);
+            assert_eq!(res, [PartStatus::Missing, PartStatus::Missing]);
+        }
+
+        #[test]
+        fn test_day_run() {
+            let run = SolutionRun {
+                output: vec![
+                    "Part 1: \x1b[1m42\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.5µs @ 100 samples)".into(),
+                    "Part 1 stats: mean 1.5µs, min 1.0µs, median 1.4µs, max 3.0µs, stddev 200.0ns @ 100 samples".into(),
+                    "Part 2: ▼  (2.0ms)".into(),
+                    "#..#".into(),
+                    "####".into(),
+                    "Peak memory: 2048 KiB".into(),
+                ],
+                errors: vec![],
+                success: true,
+                timed_out: false,
+            };
+
+            let res = parse_day_run(&run, day!(3));
+            assert_eq!(res.parts[0].status, PartStatus::Solved);
+            assert_eq!(res.parts[0].answer.as_deref(), Some("42"));
+            assert_approx_eq!(res.parts[0].nanos.unwrap(), 1500_f64);
+            assert_eq!(res.parts[0].stats.unwrap().samples, 100);
+            assert_eq!(res.parts[1].answer.as_deref(), Some("#..#\n####"));
+            assert_approx_eq!(res.parts[1].nanos.unwrap(), 2_000_000_f64);
+            assert_eq!(res.parts[1].stats, None);
+            assert_eq!(res.memory_kib, Some(2048));
+
+            let run = SolutionRun {
+                output: vec!["Part 1: ✖\rPart 1: ✖ panicked    ".into()],
+                errors: vec!["\x1b[1merror:\x1b[0m panicked at src/bin/03.rs:1:1: oh no".into()],
+                success: false,
+                timed_out: false,
+            };
+            let res = parse_day_run(&run, day!(3));
+            assert_eq!(res.parts[0].status, PartStatus::Panicked);
+            assert_eq!(res.parts[0].answer, None);
+            assert_eq!(res.parts[1].status, PartStatus::Missing);
+            assert_eq!(res.stderr, vec!["error: panicked at src/bin/03.rs:1:1: oh no"]);
+
+            let run = SolutionRun {
+                output: vec![
+                    "Part 1: ▼  (2.0ms)".into(),
+                    "#..#".into(),
+                    "####".into(),
+                    "Part 1 letters: \x1b[1mHI\x1b[0m".into(),
+                ],
+                errors: vec![],
+                success: true,
+                timed_out: false,
+            };
+            let res = parse_day_run(&run, day!(3));
+            assert_eq!(res.parts[0].answer.as_deref(), Some("HI"));
+        }
+
+        #[test]
+        fn test_verify_fails_on_panics() {
+            let run = SolutionRun {
+                output: vec![
+                    "Part 1: ✖\rPart 1: ✖ panicked    ".into(),
+                    "Part 2: ✖\rPart 2: ✖ error       ".into(),
+                ],
+                errors: vec!["\x1b[1merror:\x1b[0m panicked at src/bin/03.rs:1:1: oh no".into()],
+                success: true,
+                timed_out: false,
+            };
+
+            let res = parse_day_run(&run, day!(3));
+            assert_eq!(res.parts[0].verdict(true), Verdict::Errored("panicked"));
+            assert_eq!(res.parts[1].verdict(true), Verdict::Errored("error"));
+            assert!(has_failures(&[res]));
+
+            let run = SolutionRun {
+                output: vec!["Part 1: ✖\rPart 1: ✖             ".into()],
+                errors: vec![],
+                success: true,
+                timed_out: false,
+            };
+            let mut res = parse_day_run(&run, day!(3));
+            res.parts[1].status = PartStatus::Unsolved;
+            assert_eq!(res.parts[0].verdict(true), Verdict::Skipped("not solved"));
+            assert!(!has_failures(&[res]));
+        }
     }
 }
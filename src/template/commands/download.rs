@@ -1,6 +1,6 @@
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, config, session};
 use crate::Day;
-use std::process;
+use std::{env, process};
 
 pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
@@ -8,8 +8,28 @@ pub fn handle(day: Day) {
         process::exit(1);
     }
 
+    // fail before anything is written instead of in the middle of the download. aoc-cli can find a token on its
+    // own, so only a stored token that is known to be bad is an error.
+    match session::read().and_then(|token| session::check(&token)) {
+        Ok(_) => {}
+        Err(e @ session::Error::Missing(_)) => {
+            eprintln!("Warning: {e} Leaving it to aoc-cli to find one.");
+        }
+        Err(e @ (session::Error::Expired | session::Error::InvalidFormat(_))) if !uses_env_token() => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        Err(e) => eprintln!("Warning: could not check the session token, {e}"),
+    }
+
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Checks whether aoc-cli takes the token from `ADVENT_OF_CODE_SESSION` instead of the stored one. An explicitly
+/// configured token file is passed to aoc-cli and takes precedence over the environment.
+fn uses_env_token() -> bool {
+    config::get().session.token_file.is_none() && env::var_os("ADVENT_OF_CODE_SESSION").is_some()
+}
@@ -0,0 +1,31 @@
+use crate::template::generators;
+use crate::Day;
+use itertools::Itertools;
+use std::{fs, process};
+
+pub fn handle(day: Day, size: usize, seed: Option<u64>, out: Option<String>) {
+    let seed = seed.unwrap_or_else(rand::random);
+
+    let Some(input) = generators::generate(day, size, seed) else {
+        eprintln!(
+            "no input generator for day {day}, available days: {}",
+            generators::available().iter().join(", ")
+        );
+        process::exit(1);
+    };
+
+    match out {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, input) {
+                eprintln!("failed to write generated input to \"{path}\": {e}");
+                process::exit(1);
+            }
+            println!("Generated input for day {day} (size {size}, seed {seed}) at \"{path}\"");
+        }
+        None => {
+            print!("{input}");
+            // keep stdout clean for redirection, but make the input reproducible.
+            eprintln!("Generated input for day {day} (size {size}, seed {seed})");
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::process;
+
+use crate::template::{aoc_cli, leaderboard};
+use crate::Day;
+
+/// Shows a private leaderboard, loaded from `file` if given and fetched from the server otherwise.
+pub fn handle(id: Option<u64>, file: Option<String>, day: Option<Day>) {
+    let result = match (file, id) {
+        (Some(file), _) => leaderboard::load(&file),
+        (None, Some(id)) => {
+            let Some(year) = aoc_cli::get_year() else {
+                eprintln!("no year configured, set `year` in aoc.toml or pass --year.");
+                process::exit(1);
+            };
+            leaderboard::fetch(year, id)
+        }
+        (None, None) => {
+            eprintln!("pass the id of the leaderboard or a saved leaderboard with --file <path>.");
+            process::exit(1);
+        }
+    };
+
+    match result {
+        Ok(leaderboard) => print!("{}", leaderboard::render(&leaderboard, day)),
+        Err(e) => {
+            eprintln!("failed to load leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
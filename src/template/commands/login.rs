@@ -0,0 +1,49 @@
+use std::io::{self, BufRead, IsTerminal};
+use std::process;
+
+use crate::template::session;
+
+/// Stores the session token after checking its format and, unless `check` is false, that the server accepts it.
+/// Without `token`, the token is read from stdin so it does not end up in the shell history.
+pub fn handle(token: Option<String>, check: bool) {
+    let token = token.unwrap_or_else(|| {
+        if io::stdin().is_terminal() {
+            eprintln!(
+                "Paste the value of the `session` cookie of adventofcode.com and press enter:"
+            );
+        }
+
+        let mut line = String::new();
+        if let Err(e) = io::stdin().lock().read_line(&mut line) {
+            eprintln!("failed to read the session token: {e}");
+            process::exit(1);
+        }
+        line
+    });
+
+    let token = match session::parse_token(&token) {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if check {
+        match session::check(&token) {
+            Ok(user) => println!("Logged in as {user}."),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    match session::store(&token) {
+        Ok(path) => println!("Stored session token in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("failed to store the session token: {e}");
+            process::exit(1);
+        }
+    }
+}
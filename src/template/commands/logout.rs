@@ -0,0 +1,17 @@
+use std::process;
+
+use crate::template::session;
+
+pub fn handle() {
+    match session::remove() {
+        Ok(Some(path)) => println!("Removed session token \"{}\".", path.display()),
+        Ok(None) => println!(
+            "Not logged in, no session token at \"{}\".",
+            session::token_path().display()
+        ),
+        Err(e) => {
+            eprintln!("failed to remove the session token: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,5 +1,15 @@
 pub mod all;
 pub mod download;
+pub mod generate;
+pub mod leaderboard;
+pub mod login;
+pub mod logout;
+pub mod next;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod stars;
+pub mod status;
+pub mod verify;
+pub mod whoami;
@@ -0,0 +1,106 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use crate::template::commands::{read, scaffold};
+use crate::template::unlock::{self, Clock};
+use crate::template::{aoc_cli, session};
+use crate::Day;
+
+/// Seconds to wait after the unlock, the puzzle is not always available right on time.
+const UNLOCK_MARGIN: i64 = 1;
+
+/// How often the download is retried if the puzzle is not available yet.
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+
+fn is_prepared(day: Day) -> bool {
+    Path::new(&aoc_cli::get_puzzle_path(day)).exists()
+}
+
+/// Checks everything the download needs up front, so nothing fails after the countdown.
+fn check_setup() {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    match session::read().and_then(|token| session::check(&token)) {
+        Ok(user) => println!("Logged in as {user}."),
+        Err(e @ (session::Error::Request(_) | session::Error::IO(_))) => {
+            eprintln!("Warning: could not check the session token, {e}");
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn countdown(clock: &impl Clock, day: Day, unlock: i64) {
+    let remaining = unlock - clock.now();
+    if remaining <= 0 {
+        return;
+    }
+
+    println!(
+        "⏳ Day {} unlocks in {}.",
+        day.into_inner(),
+        unlock::format_duration(remaining)
+    );
+
+    let interactive = io::stdout().is_terminal();
+    unlock::wait_until(clock, unlock + UNLOCK_MARGIN, |remaining| {
+        if interactive {
+            print!("\r{:<12}", unlock::format_duration(remaining));
+            let _ = io::stdout().flush();
+        }
+    });
+
+    if interactive {
+        print!("\r");
+    }
+    println!("🎄 Day {} is unlocked!", day.into_inner());
+}
+
+fn download(clock: &impl Clock, day: Day) {
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match aoc_cli::download(day) {
+            Ok(_) => return,
+            Err(e) if attempt == DOWNLOAD_ATTEMPTS => {
+                eprintln!("failed to call aoc-cli: {e}");
+                process::exit(1);
+            }
+            Err(_) => {
+                eprintln!("Download failed, retrying ({attempt}/{DOWNLOAD_ATTEMPTS})...");
+                clock.sleep(Duration::from_secs(u64::from(attempt)));
+            }
+        }
+    }
+}
+
+/// Waits for the next puzzle of the configured year to unlock, then scaffolds, downloads and reads it.
+pub fn handle(clock: &impl Clock) {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("no year configured, set `year` in aoc.toml or pass --year.");
+        process::exit(1);
+    };
+
+    check_setup();
+
+    let Some((day, unlock)) = unlock::next_puzzle(year, clock.now(), is_prepared) else {
+        eprintln!("All puzzles of {year} are unlocked already.");
+        process::exit(1);
+    };
+
+    countdown(clock, day, unlock);
+
+    if Path::new(&format!("src/bin/{day}.rs")).exists() {
+        println!("Module \"src/bin/{day}.rs\" exists already, skipping scaffold.");
+    } else {
+        scaffold::handle(day);
+    }
+
+    download(clock, day);
+    read::handle(day, None);
+}
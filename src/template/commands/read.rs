@@ -1,16 +1,62 @@
-use std::process;
+use std::io::{self, IsTerminal};
+use std::process::{self, Command, Stdio};
+use std::{env, fs};
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_cli, puzzle};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+const DEFAULT_WIDTH: usize = 80;
+
+/// Returns the width of the terminal, from `COLUMNS` or `stty`.
+fn terminal_width() -> usize {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return columns;
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .and_then(|output| {
+            let size = String::from_utf8_lossy(&output.stdout).to_string();
+            size.split_whitespace().nth(1)?.parse().ok()
+        })
+        .filter(|width| *width > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Renders the downloaded puzzle description of a day, or only one part of it.
+pub fn handle(day: Day, part: Option<u8>) {
+    if part.is_some_and(|part| !(1..=2).contains(&part)) {
+        eprintln!("--part must be 1 or 2.");
         process::exit(1);
+    }
+
+    let path = aoc_cli::get_puzzle_path(day);
+    let markdown = match fs::read_to_string(&path) {
+        Ok(markdown) => markdown,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            eprintln!("No puzzle description found at \"{path}\". Run `cargo download {}` first.", day.into_inner());
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read puzzle description \"{path}\": {e}");
+            process::exit(1);
+        }
     };
+
+    let styled = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    match puzzle::render(&markdown, terminal_width(), part, styled) {
+        Some(rendered) => print!("{rendered}"),
+        None => {
+            eprintln!(
+                "Part two of day {} is not unlocked yet. Solve part one and run `cargo download {}` again.",
+                day.into_inner(),
+                day.into_inner()
+            );
+            process::exit(1);
+        }
+    }
 }
@@ -1,9 +1,10 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
+use crate::template::{aoc_cli, config};
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -39,13 +40,30 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Returns the template for new solutions, either the built-in one or the file configured in `aoc.toml`.
+/// `DAY_NUMBER` is replaced with the day.
+fn read_template() -> String {
+    match config::get().template.as_str() {
+        "default" => MODULE_TEMPLATE.into(),
+        path => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read template \"{path}\": {e}");
+            process::exit(1);
+        }),
+    }
 }
 
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+    let input_path = aoc_cli::get_input_path(day);
+    let example_path = aoc_cli::get_example_path(day);
     let module_path = format!("src/bin/{day}.rs");
+    let template = read_template();
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -56,7 +74,7 @@ pub fn handle(day: Day) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
+        template
             .replace("DAY_NUMBER", &day.into_inner().to_string())
             .as_bytes(),
     ) {
@@ -0,0 +1,337 @@
+use std::fmt::Display;
+use std::io::Read;
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::runner::{format_nanos, parse_nanos};
+use crate::template::{generators, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Sizes used when none are given: doubling from 8 until the budget is exhausted.
+const DEFAULT_SIZES: [usize; 14] = [
+    8, 16, 32, 64, 128, 256, 512, 1024, 2048, 4096, 8192, 16384, 32768, 65536,
+];
+
+/// Every size is run this many times, the fastest run is kept.
+const REPEATS: usize = 3;
+
+/// A run is killed once it takes this many times the budget.
+const TIMEOUT_FACTOR: u32 = 10;
+
+const BAR_WIDTH: usize = 30;
+
+pub fn handle(day: Day, part: u8, sizes: Option<Vec<usize>>, seed: u64, budget: Duration) {
+    if generators::get(day).is_none() {
+        eprintln!("no input generator for day {day}, see \"cargo generate\" for the available days.");
+        process::exit(1);
+    }
+
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("day {day} has not been scaffolded yet.");
+        process::exit(1);
+    }
+
+    if let Err(e) = build(day) {
+        eprintln!("failed to build the solution for day {day}: {e}");
+        process::exit(1);
+    }
+
+    let dir = Path::new("target").join("scale");
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("failed to create \"{}\": {e}", dir.display());
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}Day {day}, part {part}{ANSI_RESET} {ANSI_ITALIC}(seed {seed}, budget {budget:?}){ANSI_RESET}");
+    println!("{:>8}  {:>10}", "size", "time");
+
+    let mut samples: Vec<(usize, f64)> = vec![];
+
+    for size in sizes.unwrap_or_else(|| DEFAULT_SIZES.to_vec()) {
+        let input = generators::generate(day, size, seed).unwrap_or_default();
+        let path = dir.join(format!("{day}-{size}.txt"));
+
+        if let Err(e) = fs::write(&path, input) {
+            eprintln!("failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+
+        let nanos = (0..REPEATS)
+            .map(|_| measure(day, part, &path, budget * TIMEOUT_FACTOR))
+            .try_fold(f64::INFINITY, |min, nanos| nanos.map(|nanos| min.min(nanos)));
+
+        match nanos {
+            Ok(nanos) => {
+                println!("{size:>8}  {:>10}", format_nanos(nanos));
+                samples.push((size, nanos));
+
+                if nanos > budget.as_nanos() as f64 {
+                    println!("{ANSI_ITALIC}Stopping, size {size} exceeded the budget.{ANSI_RESET}");
+                    break;
+                }
+            }
+            Err(e) => {
+                println!("{size:>8}  {:>10}", "✖");
+                println!("{ANSI_ITALIC}Stopping, {e}.{ANSI_RESET}");
+                break;
+            }
+        }
+    }
+
+    if samples.len() < 3 {
+        println!("\nNot enough measurements to fit a complexity class.");
+        return;
+    }
+
+    println!();
+    print_chart(&samples);
+
+    let fits = fit(&samples);
+    println!("\n{:<12}  {:>7}", "complexity", "error");
+    for (idx, fit) in fits.iter().enumerate() {
+        let marker = if idx == 0 { " ← best fit" } else { "" };
+        println!("{:<12}  {:>7.3}{marker}", fit.complexity.to_string(), fit.error);
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Best fit:{ANSI_RESET} {} {ANSI_ITALIC}(empirical order n^{:.2}){ANSI_RESET}",
+        fits[0].complexity,
+        order(&samples)
+    );
+}
+
+fn build(day: Day) -> Result<(), String> {
+    let day_padded = day.to_string();
+    let status = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--bin", &day_padded])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("cargo exited with {status}"))
+    }
+}
+
+/// Runs one part of the solution on the input at `path` and returns its execution time in nanoseconds.
+fn measure(day: Day, part: u8, path: &Path, timeout: Duration) -> Result<f64, String> {
+    let day_padded = day.to_string();
+    let part_str = part.to_string();
+    let path_str = path.to_string_lossy();
+
+    let mut cmd = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--bin", &day_padded, "--"])
+        .args(["--input", &path_str, "--part", &part_str])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let mut stdout = cmd.stdout.take().ok_or("could not capture output")?;
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stdout.read_to_string(&mut output);
+        output
+    });
+
+    let timer = Instant::now();
+    while cmd.try_wait().map_err(|e| e.to_string())?.is_none() {
+        if timer.elapsed() > timeout {
+            let _ = cmd.kill();
+            let _ = cmd.wait();
+            return Err(format!("timed out after {timeout:?}"));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let output = reader.join().unwrap_or_default();
+    parse_run(&output, part)
+}
+
+/// Extracts the execution time of `part` from the output of a solution bin.
+fn parse_run(output: &str, part: u8) -> Result<f64, String> {
+    let prefix = format!("Part {part}:");
+
+    let Some(line) = output.lines().map(|l| l.trim_start_matches('\r')).find(|l| l.starts_with(&prefix)) else {
+        return Err(format!("part {part} did not report a result"));
+    };
+
+    if line.contains('✖') {
+        return Err(format!("part {part} did not produce an answer"));
+    }
+
+    line.rsplit('(')
+        .next()
+        .and_then(|s| s.split(')').next())
+        .and_then(parse_nanos)
+        .ok_or_else(|| format!("could not parse the execution time from: {line}"))
+}
+
+/// Prints one bar per size, scaled logarithmically between the fastest and the slowest run.
+fn print_chart(samples: &[(usize, f64)]) {
+    let logs: Vec<f64> = samples.iter().map(|(_, nanos)| nanos.max(1_f64).ln()).collect();
+    let min = logs.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    for ((size, nanos), log) in samples.iter().zip(logs) {
+        let ratio = if max > min { (log - min) / (max - min) } else { 1_f64 };
+        let width = 1 + (ratio * (BAR_WIDTH - 1) as f64).round() as usize;
+        println!("{size:>8}  {}  {}", "█".repeat(width) + &" ".repeat(BAR_WIDTH - width), format_nanos(*nanos));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A complexity class that measurements can be fitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Exponential,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 4] = [
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Exponential,
+    ];
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Exponential => "O(cⁿ)",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// How well a complexity class describes a series of measurements.
+#[derive(Debug, Clone, Copy)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// Root mean square of the residuals in log space, i.e. roughly the relative error of the model.
+    pub error: f64,
+}
+
+/// Fits `(size, time)` samples to every complexity class, the best fit comes first.
+///
+/// Polynomial classes are fitted as `t = a * f(n)`, the exponential class as `t = a * bⁿ`. Both are
+/// fitted in log space, so slow and fast sizes weigh the same.
+pub fn fit(samples: &[(usize, f64)]) -> Vec<Fit> {
+    let mut fits: Vec<Fit> = Complexity::ALL
+        .iter()
+        .map(|&complexity| {
+            let residuals: Vec<f64> = match complexity {
+                Complexity::Exponential => {
+                    let points: Vec<(f64, f64)> = samples.iter().map(|&(n, t)| (n as f64, t.ln())).collect();
+                    let (slope, intercept) = linear_regression(&points);
+                    points.iter().map(|(x, y)| y - (intercept + slope * x)).collect()
+                }
+                _ => {
+                    let diffs: Vec<f64> = samples
+                        .iter()
+                        .map(|&(n, t)| t.ln() - model(complexity, n as f64).ln())
+                        .collect();
+                    let offset = mean(&diffs);
+                    diffs.iter().map(|d| d - offset).collect()
+                }
+            };
+
+            let squares: Vec<f64> = residuals.iter().map(|r| r * r).collect();
+            Fit { complexity, error: mean(&squares).sqrt() }
+        })
+        .collect();
+
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// Estimates `k` in `t = a * nᵏ` from `(size, time)` samples.
+pub fn order(samples: &[(usize, f64)]) -> f64 {
+    let points: Vec<(f64, f64)> = samples.iter().map(|&(n, t)| ((n as f64).ln(), t.ln())).collect();
+    linear_regression(&points).0
+}
+
+fn model(complexity: Complexity, n: f64) -> f64 {
+    let n = n.max(2_f64);
+    match complexity {
+        Complexity::Linear => n,
+        Complexity::Linearithmic => n * n.log2(),
+        Complexity::Quadratic => n * n,
+        Complexity::Exponential => n.exp2(),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Returns slope and intercept of the least squares line through `points`.
+fn linear_regression(points: &[(f64, f64)]) -> (f64, f64) {
+    let xs: Vec<f64> = points.iter().map(|(x, _)| *x).collect();
+    let ys: Vec<f64> = points.iter().map(|(_, y)| *y).collect();
+    let (mean_x, mean_y) = (mean(&xs), mean(&ys));
+
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+
+    let slope = if variance > 0_f64 { covariance / variance } else { 0_f64 };
+    (slope, mean_y - slope * mean_x)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, order, parse_run, Complexity};
+
+    fn samples(f: impl Fn(f64) -> f64, sizes: &[usize]) -> Vec<(usize, f64)> {
+        sizes.iter().map(|&n| (n, f(n as f64))).collect()
+    }
+
+    #[test]
+    fn fits_polynomial_classes() {
+        let sizes = [16, 32, 64, 128, 256, 512, 1024];
+
+        let linear = samples(|n| 40.0 * n, &sizes);
+        assert_eq!(fit(&linear)[0].complexity, Complexity::Linear);
+
+        let linearithmic = samples(|n| 3.0 * n * n.log2(), &sizes);
+        assert_eq!(fit(&linearithmic)[0].complexity, Complexity::Linearithmic);
+
+        // a little noise should not change the verdict.
+        let quadratic: Vec<(usize, f64)> = samples(|n| 0.5 * n * n, &sizes)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (n, t))| (n, t * if idx % 2 == 0 { 1.1 } else { 0.9 }))
+            .collect();
+        assert_eq!(fit(&quadratic)[0].complexity, Complexity::Quadratic);
+        assert!((order(&quadratic) - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn fits_exponential_class() {
+        let exponential = samples(|n| 100.0 * 1.5_f64.powf(n), &[4, 8, 12, 16, 20, 24]);
+        let fits = fit(&exponential);
+        assert_eq!(fits[0].complexity, Complexity::Exponential);
+        assert!(fits[0].error < 1e-9);
+    }
+
+    #[test]
+    fn parses_run_output() {
+        assert_eq!(parse_run("Part 2: 1234 (1.5ms)\n", 2), Ok(1_500_000.0));
+        assert_eq!(parse_run("\rPart 1: 1 (2.0s)", 1), Ok(2_000_000_000.0));
+        assert!(parse_run("Part 1: ✖ error       \n", 1).is_err());
+        assert!(parse_run("Part 1: 1 (2.0s)", 2).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use crate::template::readme_stars;
+use std::process;
+
+pub fn handle() {
+    match readme_stars::update() {
+        Ok(stars) => println!("Successfully updated README with {stars} stars."),
+        Err(_) => {
+            eprintln!("Failed to update readme with stars.");
+            process::exit(1);
+        }
+    }
+}
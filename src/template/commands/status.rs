@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::template::commands::all::get_path_for_bin;
+use crate::template::readme_benchmarks::{self, Timings};
+use crate::template::{answers, aoc_cli, config, json, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+/// Everything the repository knows about a single day.
+#[derive(Clone)]
+pub struct DayStatus {
+    pub day: Day,
+    pub bin: bool,
+    pub input: bool,
+    pub example: bool,
+    pub puzzle: bool,
+    pub answers: [bool; 2],
+    /// Whether `cargo test --bin DD` passed, [`None`] if the tests were not run.
+    pub tests: Option<bool>,
+    pub bench: Option<Timings>,
+}
+
+pub fn handle(json: bool, run_tests: bool) {
+    let readme = fs::read_to_string(&config::get().readme.path).unwrap_or_default();
+    let timings = readme_benchmarks::read_timings(&readme);
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| {
+            let bin = Path::new(&get_path_for_bin(day)).exists();
+
+            if bin && run_tests && !json {
+                eprint!("\rTesting day {day}...");
+            }
+
+            DayStatus {
+                day,
+                bin,
+                input: Path::new(&aoc_cli::get_input_path(day)).exists(),
+                example: Path::new(&aoc_cli::get_example_path(day)).exists(),
+                puzzle: Path::new(&aoc_cli::get_puzzle_path(day)).exists(),
+                answers: answers::read(day).map(|answer| answer.is_some()),
+                tests: (bin && run_tests).then(|| run_day_tests(day)),
+                bench: timings.iter().find(|t| t.day == day).cloned(),
+            }
+        })
+        .collect();
+
+    if run_tests && !json {
+        eprint!("\r{}\r", " ".repeat(20));
+    }
+
+    if json {
+        println!("{}", to_json(&statuses));
+    } else {
+        println!("{}", to_table(&statuses));
+    }
+}
+
+fn run_day_tests(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn mark(value: bool) -> &'static str {
+    if value {
+        "✔"
+    } else {
+        "·"
+    }
+}
+
+fn to_table(statuses: &[DayStatus]) -> String {
+    let header = format!(
+        "{ANSI_BOLD}{:<4} {:^5} {:^5} {:^7} {:^6} {:^7} {:^5}  {}{ANSI_RESET}",
+        "Day", "Bin", "Input", "Example", "Puzzle", "Answers", "Tests", "Bench"
+    );
+
+    let rows = statuses.iter().map(|status| {
+        let answers = status.answers.map(mark).join(" ");
+        let tests = match status.tests {
+            Some(true) => "✔",
+            Some(false) => "✖",
+            None => "-",
+        };
+        let bench = status.bench.as_ref().map_or_else(
+            || "-".to_string(),
+            |t| {
+                format!(
+                    "{} / {}",
+                    t.part_1.as_deref().unwrap_or("-"),
+                    t.part_2.as_deref().unwrap_or("-")
+                )
+            },
+        );
+
+        format!(
+            "{:<4} {:^5} {:^5} {:^7} {:^6} {:^7} {:^5}  {bench}",
+            status.day.to_string(),
+            mark(status.bin),
+            mark(status.input),
+            mark(status.example),
+            mark(status.puzzle),
+            answers,
+            tests,
+        )
+    });
+
+    std::iter::once(header).chain(rows).collect::<Vec<_>>().join("\n")
+}
+
+fn to_json(statuses: &[DayStatus]) -> String {
+    let days: Vec<String> = statuses
+        .iter()
+        .map(|status| {
+            let tests = status.tests.map_or("null", |passed| if passed { "true" } else { "false" });
+            let time = |value: &Option<String>| value.as_deref().map_or_else(|| "null".to_string(), json::quote);
+            let bench = status.bench.as_ref().map_or_else(
+                || "null".to_string(),
+                |t| {
+                    format!(
+                        "{{\"part_1\": {}, \"part_2\": {}}}",
+                        time(&t.part_1),
+                        time(&t.part_2)
+                    )
+                },
+            );
+
+            format!(
+                "  {{\"day\": {}, \"bin\": {}, \"input\": {}, \"example\": {}, \"puzzle\": {}, \"answers\": [{}, {}], \"tests\": {tests}, \"bench\": {bench}}}",
+                status.day.into_inner(),
+                status.bin,
+                status.input,
+                status.example,
+                status.puzzle,
+                status.answers[0],
+                status.answers[1],
+            )
+        })
+        .collect();
+
+    format!("[\n{}\n]", days.join(",\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{to_json, to_table, DayStatus};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+
+    fn get_mock_statuses() -> Vec<DayStatus> {
+        vec![
+            DayStatus {
+                day: day!(1),
+                bin: true,
+                input: true,
+                example: true,
+                puzzle: false,
+                answers: [true, false],
+                tests: Some(true),
+                bench: Some(Timings {
+                    day: day!(1),
+                    part_1: Some("10ms".into()),
+                    part_2: None,
+                    total_nanos: 1e+7,
+                }),
+            },
+            DayStatus {
+                day: day!(2),
+                bin: false,
+                input: false,
+                example: false,
+                puzzle: false,
+                answers: [false, false],
+                tests: None,
+                bench: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn renders_table() {
+        let table = to_table(&get_mock_statuses());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("01") && lines[1].ends_with("10ms / -"), "{}", lines[1]);
+        assert!(lines[2].starts_with("02") && lines[2].ends_with("  -"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = to_json(&get_mock_statuses());
+        assert!(json.starts_with("[\n") && json.ends_with("\n]"));
+        assert!(json.contains(r#"{"day": 1, "bin": true, "input": true, "example": true, "puzzle": false, "answers": [true, false], "tests": true, "bench": {"part_1": "10ms", "part_2": null}}"#));
+        assert!(json.contains(r#""tests": null, "bench": null}"#));
+    }
+}
@@ -0,0 +1,30 @@
+use std::process;
+use std::time::Duration;
+
+use crate::template::commands::all::{self, DayRun, Options, Verdict};
+
+/// Runs every solution in release mode and checks the answers against the ones recorded in `data/answers`.
+/// Exits with an error if an answer is wrong or a part fails, unsolved parts are fine.
+pub fn handle(junit: Option<String>, timeout: Option<Duration>) {
+    let runs = all::run(&Options {
+        is_release: true,
+        verify: true,
+        junit,
+        timeout,
+        ..Options::default()
+    });
+
+    if has_failures(&runs) {
+        process::exit(1);
+    }
+}
+
+/// Checks whether any part gave a wrong answer or did not finish.
+pub fn has_failures(runs: &[DayRun]) -> bool {
+    runs.iter().flat_map(|run| &run.parts).any(|part| {
+        matches!(
+            part.verdict(true),
+            Verdict::WrongAnswer { .. } | Verdict::Errored(_)
+        )
+    })
+}
@@ -0,0 +1,16 @@
+use std::process;
+
+use crate::template::{config, session};
+
+/// Prints the user the stored session token belongs to, exits with an error if it is missing or expired.
+pub fn handle() {
+    let user = session::read().and_then(|token| session::check(&token));
+
+    match user {
+        Ok(user) => println!("Logged in to {} as {user}.", config::get().session.server),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
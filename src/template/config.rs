@@ -0,0 +1,423 @@
+/// Repository configuration, read from `aoc.toml` in the repository root.
+///
+/// Every setting has a default, so the file and each of its keys are optional. `AOC_CONFIG` points to a
+/// different file, `AOC_YEAR` overrides the year. Command-line flags take precedence over both.
+///
+/// The file supports the subset of TOML needed here: `[tables]` and `key = value` pairs with strings,
+/// integers, floats and booleans.
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs, io};
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub year: Option<u16>,
+    /// Template for new solutions, either `default` or the path to a file.
+    pub template: String,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub session: Session,
+    pub readme: Readme,
+}
+
+/// Directories of the data files. Solutions stay in `src/bin`, where cargo discovers them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paths {
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub answers: String,
+    /// File the benchmark history is recorded in.
+    pub benchmarks: String,
+    /// Directory the benchmark chart is rendered to.
+    pub assets: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bench {
+    /// Approximate time spent benchmarking a part with `--time`.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Default for `--timeout` of `all` and `verify`.
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// File holding the session token, aoc-cli falls back to `~/.adventofcode.session`.
+    pub token_file: Option<String>,
+    /// Server that `login`, `whoami` and `leaderboard` talk to.
+    pub server: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Readme {
+    pub path: String,
+    /// Whether `cargo time` updates the benchmarks in the readme.
+    pub update_benchmarks: bool,
+    pub benchmarks_marker: String,
+    pub stars_marker: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            template: "default".into(),
+            paths: Paths {
+                inputs: "data/inputs".into(),
+                examples: "data/examples".into(),
+                puzzles: "data/puzzles".into(),
+                answers: "data/answers".into(),
+                benchmarks: "data/benchmarks.csv".into(),
+                assets: ".assets".into(),
+            },
+            bench: Bench {
+                budget: Duration::from_secs(1),
+                min_samples: 10,
+                max_samples: 10000,
+                timeout: None,
+            },
+            session: Session {
+                token_file: None,
+                server: "https://adventofcode.com".into(),
+            },
+            readme: Readme {
+                path: "README.md".into(),
+                update_benchmarks: true,
+                benchmarks_marker: "<!--- benchmarking table --->".into(),
+                stars_marker: "<!--- advent_readme_stars table --->".into(),
+            },
+        }
+    }
+}
+
+/// Expands a leading `~` to the home directory.
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), env::var("HOME").or_else(|_| env::var("USERPROFILE"))) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with(['/', '\\']) => format!("{home}{rest}"),
+        _ => path.to_string(),
+    }
+}
+
+impl Paths {
+    /// Returns the directory of a data folder, e.g. `inputs` or `examples`.
+    /// Other folders are looked up in `data`.
+    pub fn dir(&self, folder: &str) -> String {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            "answers" => self.answers.clone(),
+            folder => format!("data/{folder}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum Error {
+    /// The file is not valid, with the line the problem was found on.
+    Parser(usize, String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(line, message) => write!(f, "{}:{line}: {message}", config_path()),
+            Error::IO(e) => write!(f, "could not read {}: {e}", config_path()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "a string",
+            Value::Integer(_) => "an integer",
+            Value::Float(_) => "a float",
+            Value::Boolean(_) => "a boolean",
+        }
+    }
+}
+
+fn parse_string(s: &str) -> Option<String> {
+    if let Some(literal) = s.strip_prefix('\'') {
+        return literal.strip_suffix('\'').map(String::from);
+    }
+
+    let inner = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::new();
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            '"' => return None,
+            c => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    match s {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        s if s.starts_with(['"', '\'']) => parse_string(s).map(Value::String),
+        s => {
+            let number = s.replace('_', "");
+            number
+                .parse()
+                .map(Value::Integer)
+                .or_else(|_| number.parse().map(Value::Float))
+                .ok()
+        }
+    }
+}
+
+/// Removes a trailing comment, ignoring `#` inside of strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (idx, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) => return &line[..idx],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses the file into `(line, table.key, value)` entries.
+fn parse_entries(content: &str) -> Result<Vec<(usize, String, Value)>, Error> {
+    let mut table = String::new();
+    let mut entries = vec![];
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| Error::Parser(line_number, "expected `]` after the table name".into()))?;
+            table = format!("{}.", name.trim());
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Error::Parser(line_number, format!("expected `key = value`, found `{line}`")));
+        };
+
+        let value = parse_value(value.trim())
+            .ok_or_else(|| Error::Parser(line_number, format!("invalid value `{}`", value.trim())))?;
+
+        entries.push((line_number, format!("{table}{}", key.trim()), value));
+    }
+
+    Ok(entries)
+}
+
+/// Parses the content of a config file, keys that are not present keep their default.
+pub fn parse(content: &str) -> Result<Config, Error> {
+    let mut config = Config::default();
+    // the sample counts are checked against each other at the end, errors point to the one set last.
+    let mut samples_line = 0;
+
+    for (line, key, value) in parse_entries(content)? {
+        let mismatch = |expected: &str| {
+            Error::Parser(line, format!("expected {expected} for `{key}`, found {}", value.type_name()))
+        };
+
+        let string = || match &value {
+            Value::String(s) => Ok(s.clone()),
+            _ => Err(mismatch("a string")),
+        };
+        let boolean = || match value {
+            Value::Boolean(b) => Ok(b),
+            _ => Err(mismatch("a boolean")),
+        };
+        let count = || match value {
+            Value::Integer(n) if n >= 0 => Ok(n as u128),
+            _ => Err(mismatch("a positive integer")),
+        };
+        let seconds = || match value {
+            Value::Integer(n) if n >= 0 => Ok(Duration::from_secs(n as u64)),
+            Value::Float(n) => Duration::try_from_secs_f64(n).map_err(|_| mismatch("a positive number")),
+            _ => Err(mismatch("a number of seconds")),
+        };
+
+        match key.as_str() {
+            "year" => match value {
+                Value::Integer(year) => {
+                    config.year = Some(u16::try_from(year).map_err(|_| mismatch("a year"))?);
+                }
+                _ => return Err(mismatch("a year")),
+            },
+            "template" => config.template = string()?,
+            "paths.inputs" => config.paths.inputs = string()?,
+            "paths.examples" => config.paths.examples = string()?,
+            "paths.puzzles" => config.paths.puzzles = string()?,
+            "paths.answers" => config.paths.answers = string()?,
+            "paths.benchmarks" => config.paths.benchmarks = string()?,
+            "paths.assets" => config.paths.assets = string()?,
+            "bench.budget" => config.bench.budget = seconds()?,
+            "bench.min_samples" => {
+                config.bench.min_samples = count()?;
+                samples_line = line;
+            }
+            "bench.max_samples" => {
+                config.bench.max_samples = count()?;
+                samples_line = line;
+            }
+            "bench.timeout" => config.bench.timeout = Some(seconds()?),
+            "session.token_file" => config.session.token_file = Some(string()?),
+            "session.server" => config.session.server = string()?,
+            "readme.path" => config.readme.path = string()?,
+            "readme.update_benchmarks" => config.readme.update_benchmarks = boolean()?,
+            "readme.benchmarks_marker" => config.readme.benchmarks_marker = string()?,
+            "readme.stars_marker" => config.readme.stars_marker = string()?,
+            key => return Err(Error::Parser(line, format!("unknown key `{key}`"))),
+        }
+    }
+
+    if config.bench.min_samples > config.bench.max_samples {
+        return Err(Error::Parser(samples_line, "`bench.min_samples` is larger than `bench.max_samples`".into()));
+    }
+
+    Ok(config)
+}
+
+fn config_path() -> String {
+    env::var("AOC_CONFIG").unwrap_or_else(|_| CONFIG_PATH.into())
+}
+
+/// Reads the config file, a missing file results in the defaults. `AOC_YEAR` overrides the year.
+pub fn load() -> Result<Config, Error> {
+    let mut config = match fs::read_to_string(config_path()) {
+        Ok(content) => parse(&content)?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+        Err(e) => return Err(Error::IO(e)),
+    };
+
+    if let Some(year) = env::var("AOC_YEAR").ok().and_then(|year| year.parse().ok()) {
+        config.year = Some(year);
+    }
+
+    Ok(config)
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Sets the config for this process, e.g. after applying command-line overrides.
+/// Has no effect if the config has already been used.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// Returns the config of this process, loading it on first use. Exits if the file is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        load().unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, Config, Error};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_config() {
+        let config = parse(
+            r#"
+            # comments are ignored
+            year = 2023
+            template = "templates/day.rs" # also at the end of a line
+
+            [paths]
+            inputs = 'my # inputs'
+            assets = "docs/assets"
+
+            [bench]
+            budget = 0.5
+            max_samples = 1_000
+            timeout = 30
+
+            [readme]
+            update_benchmarks = false
+            stars_marker = "<!-- \"stars\" -->"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.template, "templates/day.rs");
+        assert_eq!(config.paths.inputs, "my # inputs");
+        assert_eq!(config.paths.dir("inputs"), "my # inputs");
+        assert_eq!(config.paths.dir("examples"), "data/examples");
+        assert_eq!(config.paths.assets, "docs/assets");
+        assert_eq!(config.paths.benchmarks, "data/benchmarks.csv");
+        assert_eq!(config.bench.budget, Duration::from_millis(500));
+        assert_eq!(config.bench.max_samples, 1000);
+        assert_eq!(config.bench.timeout, Some(Duration::from_secs(30)));
+        assert!(!config.readme.update_benchmarks);
+        assert_eq!(config.readme.stars_marker, r#"<!-- "stars" -->"#);
+        assert_eq!(config.readme.path, "README.md");
+    }
+
+    #[test]
+    fn defaults_to_empty_config() {
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn reports_invalid_config() {
+        let message = |content: &str| match parse(content) {
+            Err(Error::Parser(line, message)) => format!("{line}: {message}"),
+            other => panic!("expected a parser error, got {other:?}"),
+        };
+
+        assert_eq!(message("\n[bench]\nbudget = \"fast\""), "3: expected a number of seconds for `bench.budget`, found a string");
+        assert_eq!(message("years = 2023"), "1: unknown key `years`");
+        assert_eq!(message("year = 20.23"), "1: expected a year for `year`, found a float");
+        assert_eq!(message("template = \"open"), "1: invalid value `\"open`");
+        assert_eq!(message("[paths"), "1: expected `]` after the table name");
+        assert_eq!(message("year"), "1: expected `key = value`, found `year`");
+        assert_eq!(message("[bench]\nmin_samples = 5\nmax_samples = 3"), "3: `bench.min_samples` is larger than `bench.max_samples`");
+        assert_eq!(message("[bench]\nmin_samples = 50000"), "2: `bench.min_samples` is larger than `bench.max_samples`");
+    }
+}
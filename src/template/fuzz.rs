@@ -0,0 +1,304 @@
+/// Property checks for puzzle input parsers.
+///
+/// A parser is fed its example input plus a few hundred randomly mutated and generated variants of it.
+/// The property that has to hold: it either succeeds or returns an error, but it never panics.
+use crate::template::config;
+use crate::template::runner::{catch_panic, PanicReport};
+use crate::Day;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::{env, fs};
+
+/// Number of generated inputs per parser, can be overridden with `AOC_FUZZ_CASES`.
+const DEFAULT_CASES: usize = 256;
+
+/// Seed for the random generator, can be overridden with `AOC_FUZZ_SEED` to explore other inputs.
+const DEFAULT_SEED: u64 = 2023;
+
+/// Upper bound of parser invocations spent on minimizing a panicking input.
+const SHRINK_BUDGET: usize = 2000;
+
+/// Characters that are mixed into generated inputs in addition to the characters of the seed input.
+const EXTRA_CHARS: &[char] = &[
+    ' ', '\n', '\n', ',', ':', ';', '=', '-', '+', '~', '@', '(', ')', '{', '}', '<', '>', '#', '.', '0',
+    '1', '9', 'x', 'Z', '\t', 'é', '🎄',
+];
+
+/// Replacements for numbers found in the seed input.
+const EDGE_NUMBERS: &[&str] = &[
+    "", "0", "-1", "1", "4294967296", "18446744073709551616", "99999999999999999999999", "1e9",
+    "0x1F", "NaN",
+];
+
+/// Returns the example input of `day` to seed [`check_parser`] with, or `fallback` if the example file is
+/// missing. Example files are not committed, the fallback keeps the checks running on a clean checkout.
+pub fn example_or(day: Day, fallback: &str) -> String {
+    let path = env::current_dir()
+        .unwrap()
+        .join(config::get().paths.dir("examples"))
+        .join(format!("{day}.txt"));
+    fs::read_to_string(path).unwrap_or_else(|_| fallback.to_string())
+}
+
+/// Checks that `parse` handles `seed` and random mutations of it without panicking.
+///
+/// `seed` itself has to parse successfully. Generation is deterministic, so failures are reproducible
+/// offline. A panicking input is minimized before it is reported.
+///
+/// ```
+/// # use advent_of_code::template::fuzz::check_parser;
+/// check_parser("1 2 3", |input| {
+///     input.split(' ').map(str::parse::<u32>).collect::<Result<Vec<_>, _>>()
+/// });
+/// ```
+pub fn check_parser<T, E>(seed: &str, parse: impl Fn(&str) -> Result<T, E>) {
+    let runs_cleanly = |input: &str| catch_panic(|| parse(input).is_ok());
+
+    match runs_cleanly(seed) {
+        Ok(true) => {}
+        Ok(false) => panic!("parser rejected its seed input:\n{seed}"),
+        Err(report) => panic!("parser panicked on its seed input: {report}"),
+    }
+
+    let rng_seed = env_or("AOC_FUZZ_SEED", DEFAULT_SEED);
+    let mut rng = StdRng::seed_from_u64(rng_seed);
+    let alphabet = alphabet(seed);
+
+    for case in 0..env_or("AOC_FUZZ_CASES", DEFAULT_CASES) {
+        let input = if case % 8 == 7 {
+            random_input(&mut rng, &alphabet, seed.len())
+        } else {
+            let mut input = seed.to_string();
+            for _ in 0..rng.gen_range(1..=3) {
+                input = mutate(&mut rng, &input, &alphabet);
+            }
+            input
+        };
+
+        if let Err(report) = runs_cleanly(&input) {
+            let (input, report) = shrink(input, report, |input| runs_cleanly(input).err());
+            panic!("parser panicked on case {case} (AOC_FUZZ_SEED={rng_seed}): {report}\ninput: {input:?}");
+        }
+    }
+}
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn alphabet(seed: &str) -> Vec<char> {
+    let mut chars: Vec<char> = seed.chars().chain(EXTRA_CHARS.iter().copied()).collect();
+    chars.sort_unstable();
+    chars.dedup();
+    chars
+}
+
+fn random_input(rng: &mut StdRng, alphabet: &[char], max_len: usize) -> String {
+    let len = rng.gen_range(0..=max_len.clamp(1, 200));
+    (0..len)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Applies one random mutation to `input`.
+fn mutate(rng: &mut StdRng, input: &str, alphabet: &[char]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut lines: Vec<&str> = input.split('\n').collect();
+    let random_char = alphabet[rng.gen_range(0..alphabet.len())];
+
+    match rng.gen_range(0..10) {
+        // character level
+        0 if !chars.is_empty() => {
+            chars.remove(rng.gen_range(0..chars.len()));
+        }
+        1 => chars.insert(rng.gen_range(0..=chars.len()), random_char),
+        2 if !chars.is_empty() => {
+            let idx = rng.gen_range(0..chars.len());
+            chars[idx] = random_char;
+        }
+        3 if !chars.is_empty() => {
+            let start = rng.gen_range(0..chars.len());
+            let end = rng.gen_range(start..=chars.len().min(start + 16));
+            chars.drain(start..end);
+        }
+        4 => chars.truncate(rng.gen_range(0..=chars.len())),
+        // line level
+        5 if !lines.is_empty() => {
+            lines.remove(rng.gen_range(0..lines.len()));
+            return lines.join("\n");
+        }
+        6 if !lines.is_empty() => {
+            let idx = rng.gen_range(0..lines.len());
+            lines.insert(rng.gen_range(0..=lines.len()), lines[idx]);
+            return lines.join("\n");
+        }
+        7 if lines.len() > 1 => {
+            let (a, b) = (rng.gen_range(0..lines.len()), rng.gen_range(0..lines.len()));
+            lines.swap(a, b);
+            return lines.join("\n");
+        }
+        8 => {
+            lines.insert(rng.gen_range(0..=lines.len()), "");
+            return lines.join("\n");
+        }
+        // token level
+        _ => return replace_number(rng, input),
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Replaces a random number in `input` with an edge case like an empty string or an overflowing value.
+fn replace_number(rng: &mut StdRng, input: &str) -> String {
+    let numbers: Vec<(usize, usize)> = input
+        .char_indices()
+        .filter(|(idx, c)| {
+            c.is_ascii_digit() && !input[..*idx].ends_with(|p: char| p.is_ascii_digit())
+        })
+        .map(|(start, _)| {
+            let len = input[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(input.len() - start);
+            (start, start + len)
+        })
+        .collect();
+
+    if numbers.is_empty() {
+        return input.to_string();
+    }
+
+    let (start, end) = numbers[rng.gen_range(0..numbers.len())];
+    let replacement = EDGE_NUMBERS[rng.gen_range(0..EDGE_NUMBERS.len())];
+    format!("{}{replacement}{}", &input[..start], &input[end..])
+}
+
+/// Greedily removes lines and characters from `input` as long as it keeps panicking.
+fn shrink(
+    mut input: String,
+    mut report: PanicReport,
+    panics: impl Fn(&str) -> Option<PanicReport>,
+) -> (String, PanicReport) {
+    let mut budget = SHRINK_BUDGET;
+    let mut try_candidate = |candidate: String, input: &mut String, report: &mut PanicReport| {
+        if budget == 0 {
+            return false;
+        }
+        budget -= 1;
+
+        match panics(&candidate) {
+            Some(new_report) => {
+                *input = candidate;
+                *report = new_report;
+                true
+            }
+            None => false,
+        }
+    };
+
+    // drop whole lines first, they are the cheapest way to get a small input.
+    let mut idx = 0;
+    while idx < input.split('\n').count() {
+        let candidate = input
+            .split('\n')
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if !try_candidate(candidate, &mut input, &mut report) {
+            idx += 1;
+        }
+    }
+
+    let mut chunk = input.chars().count() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < input.chars().count() {
+            let candidate: String = input
+                .chars()
+                .enumerate()
+                .filter(|(i, _)| *i < start || *i >= start + chunk)
+                .map(|(_, c)| c)
+                .collect();
+
+            if !try_candidate(candidate, &mut input, &mut report) {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    (input, report)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_parser, mutate, replace_number, shrink};
+    use crate::template::runner::catch_panic;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn accepts_robust_parser() {
+        check_parser("1 2\n3 4", |input| {
+            input
+                .split_whitespace()
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<_>, _>>()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "parser panicked on case")]
+    fn reports_panicking_parser() {
+        check_parser("1 2\n3 4", |input| {
+            input
+                .split_whitespace()
+                .map(str::parse::<u8>)
+                .collect::<Result<Vec<_>, _>>()
+                .map(|numbers| numbers[3])
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "parser rejected its seed input")]
+    fn requires_valid_seed() {
+        check_parser("a", |input| input.parse::<u8>());
+    }
+
+    #[test]
+    fn is_deterministic() {
+        let alphabet = vec!['a', 'b', '\n'];
+        let mutations = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..32)
+                .map(|_| mutate(&mut rng, "ab\nba\n12", &alphabet))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(mutations(1), mutations(1));
+    }
+
+    #[test]
+    fn replaces_numbers() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let result = replace_number(&mut rng, "x=12,y=345");
+        assert!(result.starts_with("x=") && result.contains(",y="), "{result}");
+        assert_eq!(replace_number(&mut rng, "abc"), "abc");
+    }
+
+    #[test]
+    fn shrinks_input() {
+        let panics = |input: &str| catch_panic(|| assert!(!input.contains('!'))).err();
+        let report = panics("abc\nd!e\nfgh").unwrap();
+        let (input, _) = shrink("abc\nd!e\nfgh".into(), report, panics);
+        assert_eq!(input, "!");
+    }
+}
@@ -0,0 +1,103 @@
+/// Almanacs with `size` ranges in each of the seven mapping tables.
+///
+/// Every table is built from a bijection of the value space, with a few of its ranges left out so
+/// that some values fall through unmapped, just like in the real input. The seed line holds
+/// `size / 4` (at least one) pairs of a start and a length of up to 1000.
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Number of values per range on average.
+const RANGE_LENGTH: u64 = 1000;
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let space = size as u64 * RANGE_LENGTH;
+
+    let seeds = (0..(size / 4).max(1))
+        .flat_map(|_| [rng.gen_range(0..space), rng.gen_range(1..=RANGE_LENGTH)])
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let tables = CATEGORIES.windows(2).map(|pair| {
+        let lines = mapping_table(rng, space, size)
+            .into_iter()
+            .map(|(dest, src, len)| format!("{dest} {src} {len}\n"))
+            .collect::<String>();
+        format!("{}-to-{} map:\n{lines}", pair[0], pair[1])
+    });
+
+    let mut input = format!("seeds: {seeds}\n");
+    for table in tables {
+        input.push('\n');
+        input.push_str(&table);
+    }
+    input
+}
+
+/// Splits `0..space` into `count` ranges and maps them onto a shuffled, gap-less layout of the same space.
+fn mapping_table(rng: &mut StdRng, space: u64, count: usize) -> Vec<(u64, u64, u64)> {
+    let mut cuts: Vec<u64> = index::sample(rng, space as usize - 1, count - 1)
+        .into_iter()
+        .map(|cut| cut as u64 + 1)
+        .collect();
+    cuts.sort_unstable();
+    cuts.insert(0, 0);
+    cuts.push(space);
+
+    let mut ranges: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+    ranges.shuffle(rng);
+
+    let mut dest = 0;
+    let mut table: Vec<(u64, u64, u64)> = ranges
+        .into_iter()
+        .map(|(src, len)| {
+            dest += len;
+            (dest - len, src, len)
+        })
+        .collect();
+
+    // keep at least one mapping, an empty table is not a valid input.
+    table.shuffle(rng);
+    let keep = table.len() - table.len() / 5;
+    table.truncate(keep.max(1));
+    table
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::mapping_table;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn mapping_ranges_do_not_overlap() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let table = mapping_table(&mut rng, 10_000, 40);
+        assert_eq!(table.len(), 32);
+
+        for ranges in [
+            table.iter().map(|&(dest, _, len)| (dest, len)).collect::<Vec<_>>(),
+            table.iter().map(|&(_, src, len)| (src, len)).collect(),
+        ] {
+            let mut ranges = ranges;
+            ranges.sort_unstable();
+            assert!(ranges.iter().all(|&(start, len)| len > 0 && start + len <= 10_000));
+            assert!(ranges.windows(2).all(|w| w[0].0 + w[0].1 <= w[1].0));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// Condition records with `size` rows of 4 to 20 springs.
+///
+/// Each row is derived from a random arrangement with at least one damaged spring, so it always has
+/// at least one valid solution. Half of the springs are then hidden as unknown.
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size.max(1)).map(|_| row(rng) + "\n").collect()
+}
+
+fn row(rng: &mut StdRng) -> String {
+    let len = rng.gen_range(4..=20);
+    let mut damaged: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.4)).collect();
+    if !damaged.contains(&true) {
+        damaged[rng.gen_range(0..len)] = true;
+    }
+
+    let groups = damaged
+        .split(|d| !d)
+        .filter(|group| !group.is_empty())
+        .map(|group| group.len().to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let springs: String = damaged
+        .iter()
+        .map(|&d| match (rng.gen_bool(0.5), d) {
+            (true, _) => '?',
+            (false, true) => '#',
+            (false, false) => '.',
+        })
+        .collect();
+
+    format!("{springs} {groups}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::generate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn rows_fit_their_groups() {
+        let input = generate(&mut StdRng::seed_from_u64(5), 50);
+        assert_eq!(input.lines().count(), 50);
+
+        for line in input.lines() {
+            let (springs, groups) = line.split_once(' ').unwrap();
+            let groups: Vec<usize> = groups.split(',').map(|g| g.parse().unwrap()).collect();
+            assert!(!groups.is_empty() && groups.iter().all(|&g| g > 0), "{line}");
+            assert!(groups.iter().sum::<usize>() + groups.len() - 1 <= springs.len(), "{line}");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// Square platforms of `size` by `size` tiles with 20% rounded rocks and 15% cube rocks.
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| match rng.gen_range(0..100) {
+                    0..=19 => 'O',
+                    20..=34 => '#',
+                    _ => '.',
+                })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
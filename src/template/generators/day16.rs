@@ -0,0 +1,21 @@
+/// Square contraptions of `size` by `size` tiles, about 10% of them mirrors or splitters.
+use rand::rngs::StdRng;
+use rand::Rng;
+
+const DEVICES: [char; 4] = ['/', '\\', '|', '-'];
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| match rng.gen_range(0..10) {
+                    0 => DEVICES[rng.gen_range(0..DEVICES.len())],
+                    _ => '.',
+                })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
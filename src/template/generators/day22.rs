@@ -0,0 +1,99 @@
+/// Snapshots of `size` falling bricks on the 10 by 10 footprint of the real input.
+///
+/// Bricks are 1 to 4 cubes long along a single axis and never overlap. The stack is about `size / 4`
+/// layers high, which matches the density of the real input, and grows when it runs out of space.
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::HashSet;
+
+type Cube = [usize; 3];
+
+const FOOTPRINT: usize = 10;
+const MAX_LENGTH: usize = 4;
+
+/// Failed placements after which the stack gets one layer higher.
+const MAX_ATTEMPTS: usize = 100;
+
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut occupied: HashSet<Cube> = HashSet::new();
+    let mut height = size / 4 + 1;
+    let mut attempts = 0;
+    let mut input = String::new();
+
+    for _ in 0..size.max(1) {
+        let (start, end) = loop {
+            let brick = random_brick(rng, height);
+            if cubes(brick).all(|cube| !occupied.contains(&cube)) {
+                break brick;
+            }
+
+            attempts += 1;
+            if attempts >= MAX_ATTEMPTS {
+                height += 1;
+                attempts = 0;
+            }
+        };
+
+        occupied.extend(cubes((start, end)));
+        input.push_str(&format!(
+            "{},{},{}~{},{},{}\n",
+            start[0], start[1], start[2], end[0], end[1], end[2]
+        ));
+    }
+
+    input
+}
+
+fn random_brick(rng: &mut StdRng, height: usize) -> (Cube, Cube) {
+    let axis = rng.gen_range(0..3);
+    let extent = rng.gen_range(0..MAX_LENGTH);
+
+    let mut start = [
+        rng.gen_range(0..FOOTPRINT),
+        rng.gen_range(0..FOOTPRINT),
+        rng.gen_range(1..=height),
+    ];
+    if axis < 2 {
+        start[axis] = rng.gen_range(0..FOOTPRINT - extent);
+    }
+
+    let mut end = start;
+    end[axis] += extent;
+    (start, end)
+}
+
+fn cubes((start, end): (Cube, Cube)) -> impl Iterator<Item = Cube> {
+    (start[0]..=end[0]).flat_map(move |x| {
+        (start[1]..=end[1]).flat_map(move |y| (start[2]..=end[2]).map(move |z| [x, y, z]))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{cubes, generate, Cube};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn bricks_do_not_overlap() {
+        let input = generate(&mut StdRng::seed_from_u64(9), 400);
+        let mut occupied: HashSet<Cube> = HashSet::new();
+
+        for line in input.lines() {
+            let numbers: Vec<usize> = line
+                .split(['~', ','])
+                .map(|n| n.parse().unwrap())
+                .collect();
+            let (start, end) = ([numbers[0], numbers[1], numbers[2]], [numbers[3], numbers[4], numbers[5]]);
+
+            assert!(start[2] >= 1 && end[0] < 10 && end[1] < 10, "{line}");
+            assert!((0..3).filter(|&axis| start[axis] != end[axis]).count() <= 1, "{line}");
+            assert!(cubes((start, end)).all(|cube| occupied.insert(cube)), "{line}");
+        }
+
+        assert_eq!(input.lines().count(), 400);
+    }
+}
@@ -0,0 +1,78 @@
+/// Generators for puzzle-shaped inputs at configurable scale.
+///
+/// Generated inputs follow the format and the constraints of the real puzzle input, but they can be
+/// made much larger (or smaller) to stress the performance of a solution or to provoke edge cases.
+/// What `size` counts depends on the day, see the documentation of the individual generators.
+use crate::Day;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+mod day05;
+mod day12;
+mod day14;
+mod day16;
+mod day22;
+
+/// Builds an input of the given size from the provided random generator.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Returns the input generator for `day`, if there is one.
+pub fn get(day: Day) -> Option<Generator> {
+    match day.into_inner() {
+        5 => Some(day05::generate),
+        12 => Some(day12::generate),
+        14 => Some(day14::generate),
+        16 => Some(day16::generate),
+        22 => Some(day22::generate),
+        _ => None,
+    }
+}
+
+/// Returns every day that has an input generator.
+pub fn available() -> Vec<Day> {
+    crate::all_days().filter(|day| get(*day).is_some()).collect()
+}
+
+/// Generates an input for `day`. The same `size` and `seed` always produce the same input.
+///
+/// ```
+/// # use advent_of_code::{day, template::generators};
+/// let input = generators::generate(day!(14), 3, 42).unwrap();
+/// assert_eq!(input.lines().count(), 3);
+/// ```
+pub fn generate(day: Day, size: usize, seed: u64) -> Option<String> {
+    let generator = get(day)?;
+    Some(generator(&mut StdRng::seed_from_u64(seed), size))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{available, generate};
+    use crate::day;
+
+    #[test]
+    fn lists_available_days() {
+        assert_eq!(available(), vec![day!(5), day!(12), day!(14), day!(16), day!(22)]);
+        assert_eq!(generate(day!(1), 10, 0), None);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        for day in available() {
+            assert_eq!(generate(day, 20, 7), generate(day, 20, 7), "day {day}");
+            assert_ne!(generate(day, 20, 7), generate(day, 20, 8), "day {day}");
+        }
+    }
+
+    #[test]
+    fn handles_tiny_sizes() {
+        for day in available() {
+            for size in 0..3 {
+                let input = generate(day, size, 1).unwrap();
+                assert!(input.ends_with('\n') && !input.trim().is_empty(), "day {day}: {input:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,358 @@
+/// Module that renders the results of `cargo all --report <path>` as a self-contained HTML page.
+/// Styles and charts are inlined, so the report can be shared and opened offline.
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use crate::template::benchmark_history::current_commit;
+use crate::template::commands::all::{get_path_for_bin, DayRun, PartStatus, Summary};
+use crate::template::markup::escape;
+use crate::template::runner::format_nanos;
+use crate::template::{aoc_cli, runner::Stats};
+use crate::Day;
+
+const STYLE: &str = r"
+body { font-family: sans-serif; margin: 2em auto; max-width: 1100px; color: #222; }
+h1 { margin-bottom: 0.2em; }
+.meta { color: #666; }
+table { border-collapse: collapse; width: 100%; font-size: 14px; }
+th, td { padding: 4px 8px; border-bottom: 1px solid #ddd; text-align: left; vertical-align: top; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; white-space: nowrap; }
+pre { margin: 0; line-height: 1; }
+.solved { color: #1a7f37; } .unsolved { color: #888; } .failed, .panicked, .missing { color: #cf222e; }
+.chart .row { display: flex; align-items: center; margin: 2px 0; font-size: 13px; }
+.chart .label { width: 4em; }
+.chart .bar { flex: 1; display: flex; height: 14px; background: #f3f3f3; }
+.chart .value { width: 10em; text-align: right; font-variant-numeric: tabular-nums; }
+.p1 { background: #4c8bf5; } .p2 { background: #f5a623; }
+.legend span { display: inline-block; width: 10px; height: 10px; margin: 0 4px 0 12px; }
+.spread { position: relative; width: 160px; height: 10px; background: #f3f3f3; }
+.spread .range { position: absolute; top: 3px; height: 4px; background: #4c8bf5; }
+.spread .median { position: absolute; top: 0; width: 2px; height: 10px; background: #222; }
+";
+
+fn format_memory(kib: u64) -> String {
+    if kib < 1024 {
+        format!("{kib} KiB")
+    } else {
+        format!("{:.1} MiB", kib as f64 / 1024.0)
+    }
+}
+
+fn status_label(status: PartStatus) -> (&'static str, &'static str) {
+    match status {
+        PartStatus::Solved => ("solved", "✔ solved"),
+        PartStatus::Unsolved => ("unsolved", "not solved"),
+        PartStatus::Failed => ("failed", "✖ error"),
+        PartStatus::Panicked => ("panicked", "✖ panicked"),
+        PartStatus::Missing => ("missing", "✖ no output"),
+        PartStatus::TimedOut => ("failed", "✖ timed out"),
+    }
+}
+
+/// Resolves `.` and `..` in an absolute path without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Returns the path of `target` relative to the directory `from`, both relative to `cwd` unless absolute.
+fn relative_path(cwd: &Path, from: &Path, target: &Path) -> String {
+    let from = normalize(&cwd.join(from));
+    let target = normalize(&cwd.join(target));
+
+    let common = from
+        .components()
+        .zip(target.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let ups = from.components().count() - common;
+    let parts: Vec<String> = std::iter::repeat_n("..".to_string(), ups)
+        .chain(
+            target
+                .components()
+                .skip(common)
+                .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+        )
+        .collect();
+
+    parts.join("/")
+}
+
+/// Links the source of `day` relative to the location of the report, so the links survive moving the checkout.
+fn source_link(report_path: &str, day: Day) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let report_dir = Path::new(report_path).parent().unwrap_or(Path::new(""));
+    relative_path(&cwd, report_dir, Path::new(&get_path_for_bin(day)))
+}
+
+fn day_total(run: &DayRun) -> f64 {
+    run.parts.iter().filter_map(|part| part.nanos).sum()
+}
+
+/// Renders the total time of every day as a bar, split into both parts.
+fn render_chart(runs: &[DayRun]) -> String {
+    let max = runs.iter().map(day_total).fold(0_f64, f64::max);
+    let total: f64 = runs.iter().map(day_total).sum();
+
+    let mut html = String::from(
+        r#"<p class="legend"><span class="p1"></span>Part 1<span class="p2"></span>Part 2</p><div class="chart">"#,
+    );
+    html.push('\n');
+
+    for run in runs.iter().filter(|run| day_total(run) > 0_f64) {
+        let width = |nanos: Option<f64>| nanos.unwrap_or(0_f64) / max * 100_f64;
+        let _ = writeln!(
+            html,
+            r#"<div class="row"><span class="label">Day {}</span><div class="bar"><div class="p1" style="width: {:.2}%"></div><div class="p2" style="width: {:.2}%"></div></div><span class="value">{} ({:.1}%)</span></div>"#,
+            run.day,
+            width(run.parts[0].nanos),
+            width(run.parts[1].nanos),
+            format_nanos(day_total(run)),
+            day_total(run) / total * 100_f64
+        );
+    }
+
+    html.push_str("</div>\n");
+    html
+}
+
+/// Renders min, median and max of a part on a logarithmic scale shared by all parts.
+fn render_spread(stats: &Stats, (low, high): (f64, f64)) -> String {
+    let position = |duration: Duration| {
+        let nanos = (duration.as_nanos() as f64).max(1_f64).log10();
+        ((nanos - low) / (high - low).max(f64::EPSILON) * 100_f64).clamp(0_f64, 100_f64)
+    };
+
+    let (min, median, max) = (position(stats.min), position(stats.median), position(stats.max));
+    format!(
+        r#"<div class="spread" title="min {:.1?}, median {:.1?}, max {:.1?}"><div class="range" style="left: {min:.2}%; width: {:.2}%"></div><div class="median" style="left: {median:.2}%"></div></div>"#,
+        stats.min,
+        stats.median,
+        stats.max,
+        (max - min).max(0.5)
+    )
+}
+
+fn render_table(runs: &[DayRun], report_path: &str) -> String {
+    let all_stats = runs.iter().flat_map(|run| &run.parts).filter_map(|part| part.stats);
+    let (low, high) = all_stats.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), stats| {
+        (
+            low.min((stats.min.as_nanos() as f64).max(1_f64).log10()),
+            high.max((stats.max.as_nanos() as f64).max(1_f64).log10()),
+        )
+    });
+
+    let mut html = String::from(
+        "<table>\n<tr><th>Day</th><th>Part</th><th>Status</th><th>Answer</th><th>Mean</th><th>Min</th><th>Median</th><th>Max</th><th>Std. dev.</th><th>Samples</th><th>Spread</th><th>Memory</th></tr>\n",
+    );
+
+    for run in runs {
+        for (idx, part) in run.parts.iter().enumerate() {
+            html.push_str("<tr>");
+
+            if idx == 0 {
+                let _ = write!(
+                    html,
+                    r#"<td rowspan="2"><a href="{}">Day {}</a></td>"#,
+                    escape(&source_link(report_path, run.day)),
+                    run.day
+                );
+            }
+
+            let (class, label) = status_label(part.status);
+            let answer = match &part.answer {
+                Some(answer) if answer.contains('\n') => format!("<pre>{}</pre>", escape(answer)),
+                Some(answer) => format!("<code>{}</code>", escape(answer)),
+                None => "-".into(),
+            };
+            let _ = write!(
+                html,
+                r#"<td>{}</td><td class="{class}">{label}</td><td>{answer}</td>"#,
+                idx + 1
+            );
+
+            let cell = |duration: Option<Duration>| {
+                duration.map_or("-".to_string(), |duration| format!("{duration:.1?}"))
+            };
+            let mean = part.nanos.map_or("-".to_string(), format_nanos);
+
+            match &part.stats {
+                Some(stats) => {
+                    let _ = write!(
+                        html,
+                        r#"<td class="num">{mean}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{}</td><td>{}</td>"#,
+                        cell(Some(stats.min)),
+                        cell(Some(stats.median)),
+                        cell(Some(stats.max)),
+                        cell(Some(stats.stddev)),
+                        stats.samples,
+                        render_spread(stats, (low, high))
+                    );
+                }
+                None => {
+                    let samples = if part.nanos.is_some() { "1" } else { "-" };
+                    let _ = write!(
+                        html,
+                        r#"<td class="num">{mean}</td><td class="num">-</td><td class="num">-</td><td class="num">-</td><td class="num">-</td><td class="num">{samples}</td><td></td>"#
+                    );
+                }
+            }
+
+            if idx == 0 {
+                let memory = run.memory_kib.map_or("-".to_string(), format_memory);
+                let _ = write!(html, r#"<td class="num" rowspan="2">{memory}</td>"#);
+            }
+
+            html.push_str("</tr>\n");
+        }
+    }
+
+    html.push_str("</table>\n");
+    html
+}
+
+/// Renders the report for the runs of `cargo all`. `report_path` is where the report will be written,
+/// links to the solutions are relative to it.
+pub fn render(runs: &[DayRun], summary: &Summary, report_path: &str) -> String {
+    let title = match aoc_cli::get_year() {
+        Some(year) => format!("Advent of Code {year}"),
+        None => "Advent of Code".into(),
+    };
+    let total: f64 = runs.iter().map(day_total).sum();
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{title} report</title>\n<style>{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(html, "<h1>{title}</h1>");
+    let _ = writeln!(
+        html,
+        r#"<p class="meta">Commit <code>{}</code> · {summary} · Total <strong>{}</strong></p>"#,
+        escape(&current_commit()),
+        format_nanos(total)
+    );
+
+    html.push_str("<h2>Time per day</h2>\n");
+    html.push_str(&render_chart(runs));
+    html.push_str("<h2>Days</h2>\n");
+    html.push_str(&render_table(runs, report_path));
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use super::{relative_path, render};
+    use crate::day;
+    use crate::template::commands::all::child_commands::{parse_day_run, SolutionRun};
+    use crate::template::commands::all::{DayRun, PartRun, PartStatus, Summary};
+    use crate::template::runner::Stats;
+
+    fn get_mock_runs() -> Vec<DayRun> {
+        let stats = Stats {
+            samples: 100,
+            mean: Duration::from_micros(2),
+            min: Duration::from_micros(1),
+            median: Duration::from_micros(2),
+            max: Duration::from_micros(5),
+            stddev: Duration::from_nanos(300),
+        };
+
+        vec![
+            DayRun {
+                day: day!(1),
+                parts: [
+                    PartRun {
+                        status: PartStatus::Solved,
+                        answer: Some("<42>".into()),
+                        nanos: Some(2000.0),
+                        stats: Some(stats),
+                        expected: None,
+                    },
+                    PartRun {
+                        status: PartStatus::Panicked,
+                        answer: None,
+                        nanos: None,
+                        stats: None,
+                        expected: None,
+                    },
+                ],
+                memory_kib: Some(2048),
+                stderr: vec![],
+            },
+            DayRun::unsolved(day!(2)),
+        ]
+    }
+
+    #[test]
+    fn renders_report() {
+        let summary = Summary {
+            solved: 1,
+            unsolved: 2,
+            failed: 0,
+            panicked: 1,
+        };
+        let html = render(&get_mock_runs(), &summary, "report.html");
+
+        assert!(html.starts_with("<!DOCTYPE html>") && html.ends_with("</html>\n"));
+        assert!(html.contains("<code>&lt;42&gt;</code>"));
+        assert!(html.contains(r#"<a href="src/bin/01.rs">Day 01</a>"#));
+        assert!(html.contains("✖ panicked"));
+        assert!(html.contains("2.0 MiB"));
+        assert!(html.contains("1 solved, 0 failed, 1 panicked, 2 not solved"));
+        // one bar for the only timed day.
+        assert_eq!(html.matches(r#"<div class="row">"#).count(), 1);
+        assert_eq!(html.matches(r#"<div class="spread""#).count(), 1);
+        // no external assets.
+        assert!(!html.contains("src=") && !html.contains("http"));
+    }
+
+    #[test]
+    fn reports_panics_from_runner_output() {
+        let run = SolutionRun {
+            output: vec![
+                "Part 1: ✖\rPart 1: ✖ panicked    ".into(),
+                "Part 2: ✖\rPart 2: ✖ error       ".into(),
+            ],
+            errors: vec!["\x1b[1merror:\x1b[0m panicked at src/bin/01.rs:1:1: oh no".into()],
+            success: true,
+            timed_out: false,
+        };
+        let runs = vec![parse_day_run(&run, day!(1))];
+
+        let mut summary = Summary::default();
+        runs[0].parts.iter().for_each(|part| summary.add(part.status));
+        let html = render(&runs, &summary, "report.html");
+
+        assert!(html.contains("✖ panicked"));
+        assert!(html.contains("✖ error"));
+        assert!(!html.contains(r#"class="unsolved""#));
+        assert!(html.contains("0 solved, 1 failed, 1 panicked, 0 not solved"));
+    }
+
+    #[test]
+    fn links_relative_to_report() {
+        let cwd = Path::new("/repo");
+        let target = Path::new("./src/bin/01.rs");
+        assert_eq!(relative_path(cwd, Path::new(""), target), "src/bin/01.rs");
+        assert_eq!(relative_path(cwd, Path::new("target/reports"), target), "../../src/bin/01.rs");
+        assert_eq!(relative_path(cwd, Path::new("/tmp"), target), "../repo/src/bin/01.rs");
+        assert_eq!(relative_path(cwd, Path::new("../other"), target), "../repo/src/bin/01.rs");
+    }
+}
@@ -0,0 +1,310 @@
+/// Minimal JSON reader for the responses of the Advent of Code API, e.g. private leaderboards.
+/// Objects keep the order of their keys. Strings written by the template are quoted with [`quote`].
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of `key` if this is an object that contains it.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns integers, also when they are encoded as strings like the years of the API.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 => Some(*n as i64),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// Byte offset the problem was found at.
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid JSON at byte {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, Error> {
+        Err(Error {
+            position: self.position,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        if self.input[self.position..].starts_with(token) {
+            self.position += token.len();
+            Ok(())
+        } else {
+            self.error(format!("expected `{token}`"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some('n') => self.expect("null").map(|()| Value::Null)?,
+            Some('t') => self.expect("true").map(|()| Value::Boolean(true))?,
+            Some('f') => self.expect("false").map(|()| Value::Boolean(false))?,
+            Some('"') => Value::String(self.string()?),
+            Some('[') => self.array()?,
+            Some('{') => self.object()?,
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number()?,
+            Some(c) => return self.error(format!("unexpected character {c:?}")),
+            None => return self.error("unexpected end of input"),
+        };
+        self.skip_whitespace();
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let rest = &self.input[self.position..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+
+        match rest[..len].parse() {
+            Ok(n) => {
+                self.position += len;
+                Ok(Value::Number(n))
+            }
+            Err(_) => self.error(format!("invalid number `{}`", &rest[..len])),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect("\"")?;
+        let mut result = String::new();
+
+        loop {
+            let Some(c) = self.peek() else {
+                return self.error("unterminated string");
+            };
+            self.position += c.len_utf8();
+
+            match c {
+                '"' => return Ok(result),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return self.error("unterminated string");
+                    };
+                    self.position += 1;
+                    result.push(match escape {
+                        '"' | '\\' | '/' => escape,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        c => return self.error(format!("invalid escape `\\{c}`")),
+                    });
+                }
+                c => result.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, Error> {
+        let digits = self.input.get(self.position..self.position + 4);
+        match digits.and_then(|digits| u32::from_str_radix(digits, 16).ok()) {
+            Some(n) => {
+                self.position += 4;
+                Ok(n)
+            }
+            None => self.error("expected 4 hexadecimal digits"),
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            // characters outside of the basic plane are encoded as surrogate pairs.
+            self.expect("\\u")?;
+            let low = self.hex4()?;
+            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+        } else {
+            high
+        };
+
+        Ok(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn array(&mut self) -> Result<Value, Error> {
+        self.expect("[")?;
+        self.skip_whitespace();
+        let mut items = vec![];
+
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Value::Array(items));
+        }
+
+        loop {
+            items.push(self.value()?);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(Value::Array(items));
+                }
+                _ => return self.error("expected `,` or `]`"),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Error> {
+        self.expect("{")?;
+        self.skip_whitespace();
+        let mut entries = vec![];
+
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Value::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            entries.push((key, self.value()?));
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Value::Object(entries));
+                }
+                _ => return self.error("expected `,` or `}`"),
+            }
+        }
+    }
+}
+
+/// Parses a JSON document.
+pub fn parse(input: &str) -> Result<Value, Error> {
+    let mut parser = Parser { input, position: 0 };
+    let value = parser.value()?;
+
+    if parser.position < input.len() {
+        return parser.error("unexpected content after the value");
+    }
+
+    Ok(value)
+}
+
+/// Quotes a string as a JSON string literal, escaping quotes, backslashes and control characters.
+pub fn quote(value: &str) -> String {
+    let escaped: String = value
+        .chars()
+        .flat_map(|c| match c {
+            '"' => vec!['\\', '"'],
+            '\\' => vec!['\\', '\\'],
+            c if c.is_control() => format!("\\u{:04x}", c as u32).chars().collect(),
+            c => vec![c],
+        })
+        .collect();
+
+    format!("\"{escaped}\"")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, quote, Error, Value};
+
+    #[test]
+    fn parses_values() {
+        let value = parse(r#" {"a": [1, -2.5e1, true, null], "b": {"c": "x\"é🎄"}, "d": {}} "#).unwrap();
+
+        assert_eq!(
+            value.get("a"),
+            Some(&Value::Array(vec![
+                Value::Number(1.0),
+                Value::Number(-25.0),
+                Value::Boolean(true),
+                Value::Null
+            ]))
+        );
+        assert_eq!(value.get("b").and_then(|b| b.get("c")).and_then(Value::as_str), Some("x\"é🎄"));
+        assert_eq!(value.get("d").and_then(Value::as_object).map(<[_]>::len), Some(0));
+        assert_eq!(Value::String("2023".into()).as_i64(), Some(2023));
+    }
+
+    #[test]
+    fn quotes_strings() {
+        assert_eq!(quote("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+
+        let value = "tab\t \"quoted\" \\ é🎄\u{7f}";
+        assert_eq!(parse(&quote(value)).unwrap(), Value::String(value.into()));
+    }
+
+    #[test]
+    fn reports_invalid_json() {
+        let message = |input: &str| parse(input).map_err(|Error { position, message }| (position, message));
+
+        assert_eq!(message(r#"{"a" 1}"#), Err((5, "expected `:`".into())));
+        assert_eq!(message("[1, 2"), Err((5, "expected `,` or `]`".into())));
+        assert_eq!(message(r#""abc"#), Err((4, "unterminated string".into())));
+        assert_eq!(message("1 2"), Err((2, "unexpected content after the value".into())));
+    }
+}
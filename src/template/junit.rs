@@ -0,0 +1,166 @@
+/// Module that renders the results of `cargo all` and `cargo verify` as JUnit XML for CI dashboards.
+/// Every day is a test suite with one test case per part.
+use std::fmt::Write;
+
+use crate::template::commands::all::{DayRun, PartRun, Verdict};
+use crate::template::markup::escape;
+
+fn seconds(part: &PartRun) -> f64 {
+    part.nanos.unwrap_or(0_f64) / 1e9
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+    time: f64,
+}
+
+impl Counts {
+    fn add(&mut self, part: &PartRun, verdict: &Verdict) {
+        self.tests += 1;
+        self.time += seconds(part);
+        match verdict {
+            Verdict::Passed => {}
+            Verdict::WrongAnswer { .. } => self.failures += 1,
+            Verdict::Errored(_) => self.errors += 1,
+            Verdict::Skipped(_) => self.skipped += 1,
+        }
+    }
+
+    fn attributes(&self) -> String {
+        format!(
+            r#"tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.6}""#,
+            self.tests, self.failures, self.errors, self.skipped, self.time
+        )
+    }
+}
+
+fn render_case(run: &DayRun, part: u8, result: &PartRun, verdict: &Verdict) -> String {
+    let mut xml = format!(
+        r#"    <testcase classname="day{}" name="part_{part}" time="{:.6}""#,
+        run.day,
+        seconds(result)
+    );
+
+    match verdict {
+        Verdict::Passed => xml.push_str("/>\n"),
+        Verdict::WrongAnswer { expected } => {
+            let _ = writeln!(
+                xml,
+                ">\n      <failure message=\"wrong answer\" type=\"WrongAnswer\">expected {}, got {}</failure>\n    </testcase>",
+                escape(expected),
+                escape(result.answer.as_deref().unwrap_or_default())
+            );
+        }
+        Verdict::Errored(reason) => {
+            let _ = writeln!(xml, ">\n      <error message=\"{reason}\"/>");
+            if !run.stderr.is_empty() {
+                let _ = writeln!(xml, "      <system-err>{}</system-err>", escape(&run.stderr.join("\n")));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        Verdict::Skipped(reason) => {
+            let _ = writeln!(xml, ">\n      <skipped message=\"{reason}\"/>\n    </testcase>");
+        }
+    }
+
+    xml
+}
+
+/// Renders the runs of all days. With `verify`, solved parts are checked against the recorded answers.
+pub fn render(runs: &[DayRun], verify: bool) -> String {
+    let mut total = Counts::default();
+    let mut suites = String::new();
+
+    for run in runs {
+        let mut counts = Counts::default();
+        let mut cases = String::new();
+
+        for (part, result) in (1..).zip(&run.parts) {
+            let verdict = result.verdict(verify);
+            counts.add(result, &verdict);
+            total.add(result, &verdict);
+            cases.push_str(&render_case(run, part, result, &verdict));
+        }
+
+        let _ = write!(
+            suites,
+            "  <testsuite name=\"day{}\" {}>\n{cases}  </testsuite>\n",
+            run.day,
+            counts.attributes()
+        );
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"advent_of_code\" {}>\n{suites}</testsuites>\n",
+        total.attributes()
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::day;
+    use crate::template::commands::all::{DayRun, PartRun, PartStatus};
+
+    fn part(status: PartStatus, answer: Option<&str>, expected: Option<&str>) -> PartRun {
+        PartRun {
+            status,
+            answer: answer.map(String::from),
+            nanos: Some(2000.0),
+            stats: None,
+            expected: expected.map(String::from),
+        }
+    }
+
+    fn get_mock_runs() -> Vec<DayRun> {
+        vec![
+            DayRun {
+                day: day!(1),
+                parts: [
+                    part(PartStatus::Solved, Some("42"), Some("42")),
+                    part(PartStatus::Solved, Some("<1>"), Some("2")),
+                ],
+                memory_kib: None,
+                stderr: vec![],
+            },
+            DayRun {
+                day: day!(2),
+                parts: [
+                    part(PartStatus::Panicked, None, None),
+                    part(PartStatus::TimedOut, None, None),
+                ],
+                memory_kib: None,
+                stderr: vec!["error: panicked at src/bin/02.rs:3:5: <oh & no>\x07".into()],
+            },
+            DayRun::unsolved(day!(3)),
+        ]
+    }
+
+    #[test]
+    fn renders_junit() {
+        let xml = render(&get_mock_runs(), true);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"advent_of_code\" tests=\"6\" failures=\"1\" errors=\"2\" skipped=\"2\" time=\"0.000008\">"));
+        assert!(xml.contains(r#"<testsuite name="day01" tests="2" failures="1" errors="0" skipped="0" time="0.000004">"#));
+        assert!(xml.contains(r#"<testcase classname="day01" name="part_1" time="0.000002"/>"#));
+        assert!(xml.contains(r#"<failure message="wrong answer" type="WrongAnswer">expected 2, got &lt;1&gt;</failure>"#));
+        assert!(xml.contains(r#"<error message="panicked"/>"#));
+        assert!(xml.contains(r#"<error message="timed out"/>"#));
+        assert!(xml.contains("<system-err>error: panicked at src/bin/02.rs:3:5: &lt;oh &amp; no&gt;</system-err>"));
+        assert_eq!(xml.matches(r#"<skipped message="not solved"/>"#).count(), 2);
+    }
+
+    #[test]
+    fn skips_unrecorded_answers_when_verifying() {
+        let mut runs = get_mock_runs();
+        runs[0].parts[1].expected = None;
+
+        assert!(render(&runs, true).contains(r#"<skipped message="no recorded answer"/>"#));
+        assert!(!render(&runs, false).contains("skipped message=\"no recorded answer\""));
+    }
+}
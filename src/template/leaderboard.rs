@@ -0,0 +1,361 @@
+/// Module that loads and renders private leaderboards.
+/// The JSON comes from the API at `/<year>/leaderboard/private/view/<id>.json` or from a saved copy of it.
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::{fs, io};
+
+use crate::template::unlock::{format_duration, unlock_timestamp};
+use crate::template::{json, session};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Json(json::Error),
+    /// The JSON is valid but does not look like a leaderboard.
+    Format(String),
+    NotFound(u64),
+    Session(session::Error),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Json(e) => write!(f, "{e}"),
+            Error::Format(message) => write!(f, "unexpected leaderboard format: {message}"),
+            Error::NotFound(id) => write!(
+                f,
+                "leaderboard {id} not found, check the id and that you are a member of it"
+            ),
+            Error::Session(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<json::Error> for Error {
+    fn from(e: json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl From<session::Error> for Error {
+    fn from(e: session::Error) -> Self {
+        Error::Session(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Unix timestamps of the stars per day.
+    pub days: BTreeMap<u8, [Option<i64>; 2]>,
+}
+
+impl Member {
+    /// Returns the name shown on the website, which anonymous users do not have.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn last_star(&self) -> i64 {
+        self.days.values().flatten().flatten().copied().max().unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub year: u16,
+    /// Members ordered by rank.
+    pub members: Vec<Member>,
+}
+
+fn field<'a>(value: &'a json::Value, key: &str, context: &str) -> Result<&'a json::Value, Error> {
+    value
+        .get(key)
+        .ok_or_else(|| Error::Format(format!("missing `{key}` in {context}")))
+}
+
+fn integer(value: &json::Value, key: &str, context: &str) -> Result<i64, Error> {
+    field(value, key, context)?
+        .as_i64()
+        .ok_or_else(|| Error::Format(format!("`{key}` in {context} is not an integer")))
+}
+
+fn parse_member(id: &str, value: &json::Value) -> Result<Member, Error> {
+    let context = format!("member {id}");
+    let mut days = BTreeMap::new();
+
+    let levels = field(value, "completion_day_level", &context)?
+        .as_object()
+        .ok_or_else(|| Error::Format(format!("`completion_day_level` in {context} is not an object")))?;
+
+    for (day, parts) in levels {
+        let day: u8 = day
+            .parse()
+            .map_err(|_| Error::Format(format!("invalid day `{day}` in {context}")))?;
+        let context = format!("day {day} of {context}");
+        let mut stars = [None, None];
+
+        for (part, star) in stars.iter_mut().enumerate() {
+            if let Some(level) = parts.get(&(part + 1).to_string()) {
+                *star = Some(integer(level, "get_star_ts", &context)?);
+            }
+        }
+
+        days.insert(day, stars);
+    }
+
+    Ok(Member {
+        id: integer(value, "id", &context)? as u64,
+        name: value.get("name").and_then(json::Value::as_str).map(String::from),
+        local_score: integer(value, "local_score", &context)? as u64,
+        stars: integer(value, "stars", &context)? as u64,
+        days,
+    })
+}
+
+/// Parses the JSON of a private leaderboard and ranks its members like the website does.
+pub fn parse(input: &str) -> Result<Leaderboard, Error> {
+    let value = json::parse(input)?;
+
+    let mut members = field(&value, "members", "the leaderboard")?
+        .as_object()
+        .ok_or_else(|| Error::Format("`members` is not an object".into()))?
+        .iter()
+        .map(|(id, member)| parse_member(id, member))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.last_star().cmp(&b.last_star()))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let year = integer(&value, "event", "the leaderboard")?;
+
+    Ok(Leaderboard {
+        owner_id: integer(&value, "owner_id", "the leaderboard")? as u64,
+        year: u16::try_from(year).map_err(|_| Error::Format(format!("invalid event `{year}`")))?,
+        members,
+    })
+}
+
+/// Loads a leaderboard from a saved JSON file.
+pub fn load(path: &str) -> Result<Leaderboard, Error> {
+    parse(&fs::read_to_string(path)?)
+}
+
+/// Fetches a leaderboard from the configured server with the stored session token.
+/// The website asks to not request a leaderboard more than once every 15 minutes.
+pub fn fetch(year: u16, id: u64) -> Result<Leaderboard, Error> {
+    let response = session::fetch(&format!("/{year}/leaderboard/private/view/{id}.json"))?;
+
+    match response.status {
+        200 if response.body.trim_start().starts_with('{') => parse(&response.body),
+        // the website redirects to the leaderboard overview for leaderboards one is not a member of.
+        200 | 302 | 404 => Err(Error::NotFound(id)),
+        status => Err(session::parse_user(&response)
+            .err()
+            .unwrap_or(session::Error::Request(format!("unexpected status {status}")))
+            .into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn stars_grid(member: &Member, days: u8) -> String {
+    (1..=days)
+        .map(|day| match member.days.get(&day) {
+            Some([Some(_), Some(_)]) => '★',
+            Some([Some(_), None]) => '☆',
+            _ => '·',
+        })
+        .collect()
+}
+
+fn render_rankings(leaderboard: &Leaderboard, out: &mut String) {
+    let days = leaderboard
+        .members
+        .iter()
+        .flat_map(|member| member.days.keys())
+        .copied()
+        .max()
+        .unwrap_or(1);
+    let width = leaderboard
+        .members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    if days >= 10 {
+        let tens: String = (1..=days)
+            .map(|day| if day < 10 { ' ' } else { char::from(b'0' + day / 10) })
+            .collect();
+        let _ = writeln!(out, "{:20}{:width$}  {tens}", "", "");
+    }
+
+    let ones: String = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+    let _ = writeln!(out, "   #  Score  Stars  {:width$}  {ones}", "Name");
+
+    for (rank, member) in (1..).zip(&leaderboard.members) {
+        let _ = writeln!(
+            out,
+            "{rank:>3}.  {:>5}  {:>5}  {:width$}  {}",
+            member.local_score,
+            member.stars,
+            member.display_name(),
+            stars_grid(member, days)
+        );
+    }
+}
+
+fn render_day(leaderboard: &Leaderboard, day: Day, out: &mut String) {
+    let unlock = unlock_timestamp(leaderboard.year, day);
+    let mut finishers: Vec<(&Member, [Option<i64>; 2])> = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| Some((member, *member.days.get(&day.into_inner())?)))
+        .filter(|(_, stars)| stars[0].is_some())
+        .collect();
+
+    if finishers.is_empty() {
+        return;
+    }
+
+    finishers.sort_by_key(|(member, [part_1, part_2])| (part_2.is_none(), *part_2, *part_1, member.id));
+
+    let width = finishers
+        .iter()
+        .map(|(member, _)| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let _ = writeln!(out, "\nDay {}", day.into_inner());
+    let _ = writeln!(out, "   #  {:width$}  {:>11}  {:>11}  {:>12}", "Name", "Part 1", "Part 2", "Delta");
+
+    for (rank, (member, [part_1, part_2])) in (1..).zip(&finishers) {
+        let since_unlock = |ts: &Option<i64>| ts.map_or("-".into(), |ts| format_duration(ts - unlock));
+        let delta = match (part_1, part_2) {
+            (Some(part_1), Some(part_2)) => format!("+{}", format_duration(part_2 - part_1)),
+            _ => "-".into(),
+        };
+
+        let _ = writeln!(
+            out,
+            "{rank:>3}.  {:width$}  {:>11}  {:>11}  {delta:>12}",
+            member.display_name(),
+            since_unlock(part_1),
+            since_unlock(part_2),
+        );
+    }
+}
+
+/// Renders the rankings and, for every day or only `day`, when members got their stars relative to the
+/// unlock of the puzzle and how long part two took them.
+pub fn render(leaderboard: &Leaderboard, day: Option<Day>) -> String {
+    let mut out = format!(
+        "Private leaderboard {} ({}), {} members\n\n",
+        leaderboard.owner_id,
+        leaderboard.year,
+        leaderboard.members.len()
+    );
+
+    render_rankings(leaderboard, &mut out);
+
+    match day {
+        Some(day) => render_day(leaderboard, day, &mut out),
+        None => {
+            for day in crate::all_days() {
+                render_day(leaderboard, day, &mut out);
+            }
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, render, Error};
+    use crate::day;
+
+    const FIXTURE: &str = include_str!("fixtures/leaderboard.json");
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = parse(FIXTURE).unwrap();
+        assert_eq!((leaderboard.owner_id, leaderboard.year), (1_234_567, 2023));
+
+        let names: Vec<String> = leaderboard.members.iter().map(|m| m.display_name()).collect();
+        assert_eq!(names, ["Ada Lovelace", "Grace Hopper", "(anonymous user #3456789)", "Alan Turing"]);
+
+        let ada = &leaderboard.members[0];
+        assert_eq!((ada.local_score, ada.stars), (22, 5));
+        assert_eq!(ada.days[&2], [Some(1_701_494_100), Some(1_701_494_592)]);
+        assert_eq!(leaderboard.members[3].days.len(), 0);
+    }
+
+    #[test]
+    fn reports_invalid_leaderboards() {
+        assert!(matches!(parse(r#"{"members": {}"#), Err(Error::Json(_))));
+
+        let message = |input: &str| match parse(input) {
+            Err(Error::Format(message)) => message,
+            other => panic!("expected a format error, got {other:?}"),
+        };
+        assert_eq!(message("[]"), "missing `members` in the leaderboard");
+        assert_eq!(
+            message(r#"{"members": {"1": {"id": 1, "stars": 0, "local_score": 0}}}"#),
+            "missing `completion_day_level` in member 1"
+        );
+    }
+
+    #[test]
+    fn renders_leaderboard() {
+        let leaderboard = parse(FIXTURE).unwrap();
+
+        let expected = [
+            "Private leaderboard 1234567 (2023), 4 members",
+            "",
+            "   #  Score  Stars  Name                       123",
+            "  1.     22      5  Ada Lovelace               ★★☆",
+            "  2.     20      5  Grace Hopper               ★★☆",
+            "  3.      4      1  (anonymous user #3456789)  ☆··",
+            "  4.      0      0  Alan Turing                ···",
+            "",
+            "Day 2",
+            "   #  Name               Part 1       Part 2         Delta",
+            "  1.  Ada Lovelace     00:15:00     00:23:12     +00:08:12",
+            "  2.  Grace Hopper     00:10:00  1d 01:00:00  +1d 00:50:00",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(render(&leaderboard, Some(day!(2))), expected);
+
+        let all = render(&leaderboard, None);
+        assert!(all.contains("\nDay 1\n") && all.contains("\nDay 3\n") && !all.contains("\nDay 4\n"));
+        assert!(all.contains("  3.  (anonymous user #3456789)     00:01:40            -             -"));
+    }
+}
@@ -1,10 +1,25 @@
 use crate::Day;
 use std::{env, fs};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod benchmark_history;
 pub mod commands;
+pub mod config;
+pub mod fuzz;
+pub mod generators;
+pub mod html_report;
+pub mod json;
+pub mod junit;
+pub mod leaderboard;
+pub mod markup;
+pub mod puzzle;
+pub mod readme;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod session;
+pub mod unlock;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -14,23 +29,36 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(config::get().paths.dir(folder)).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// By default, the parts are the functions `part_one` and `part_two`. To keep several implementations
+/// of a part, list them per part. The first one is the default, the others can be run with
+/// `--variant <name>` or compared with `--compare-variants`:
+///
+/// ```ignore
+/// advent_of_code::solution!(5, [part_one], [part_two, part_two_brute_force]);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        $crate::solution!($day, [part_one], [part_two]);
+    };
+    ($day:expr, [$($part_one:ident),+ $(,)?], [$($part_two:ident),+ $(,)?] $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = read_input(DAY);
+            run_solution(DAY, input.as_str(), [
+                vec![$(Variant::new(stringify!($part_one), $part_one)),+],
+                vec![$(Variant::new(stringify!($part_two), $part_two)),+],
+            ]);
         }
     };
 }
@@ -0,0 +1,399 @@
+/// Module that renders downloaded puzzle descriptions in the terminal.
+/// aoc-cli saves them as markdown: headings are underlined, `*emphasis*` marks the highlighted parts of the
+/// puzzle and examples are fenced code blocks. Leftover `<em>` tags and HTML entities are handled as well.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_HEADING: &str = "\x1b[1;32m";
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    ListItem(String),
+    Code(Vec<String>),
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut lines = markdown.lines().peekable();
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut blocks);
+            let code = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .map(String::from)
+                .collect();
+            blocks.push(Block::Code(code));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else if lines.peek().is_some_and(|next| is_underline(next)) && paragraph.is_empty() {
+            lines.next();
+            blocks.push(Block::Heading(trimmed.to_string()));
+        } else if trimmed.starts_with('#') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(trimmed.trim_start_matches('#').trim().to_string()));
+        } else if let Some(item) = trimmed.strip_prefix("* ").or_else(|| trimmed.strip_prefix("- ")) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::ListItem(item.to_string()));
+        } else if is_underline(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            // continuation lines of a list item belong to it.
+            match blocks.last_mut() {
+                Some(Block::ListItem(item)) if paragraph.is_empty() && line.starts_with(' ') => {
+                    item.push(' ');
+                    item.push_str(trimmed);
+                }
+                _ => paragraph.push(trimmed),
+            }
+        }
+    }
+
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    em: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn ansi(self) -> String {
+        let mut codes = String::new();
+        if self.code {
+            codes.push_str(ANSI_CODE);
+        }
+        if self.em {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.link {
+            codes.push_str(ANSI_UNDERLINE);
+        }
+        codes
+    }
+}
+
+fn decode_entity(rest: &str) -> Option<(char, usize)> {
+    let end = rest.find(';').filter(|end| *end <= 8)?;
+    let c = match &rest[1..end] {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        "apos" | "#39" => '\'',
+        "nbsp" => ' ',
+        _ => return None,
+    };
+    Some((c, end + 1))
+}
+
+/// Returns the name of an HTML tag at the start of `rest` and its length. Only the tags of the puzzle pages
+/// are recognized, so comparisons like `a < b` stay intact.
+fn parse_tag(rest: &str) -> Option<(&str, usize)> {
+    let end = rest.find('>')?;
+    let tag = rest[1..end].split_whitespace().next()?;
+    let name = tag.trim_start_matches('/');
+    matches!(name, "em" | "code" | "span" | "a" | "p" | "pre" | "ul" | "li" | "h2" | "article")
+        .then_some((tag, end + 1))
+}
+
+/// Splits the inline markup of a paragraph into characters with their style.
+/// Without `styled`, the markers are kept so the emphasis stays visible in plain text.
+fn parse_inline(text: &str, styled: bool) -> Vec<(char, Style)> {
+    let mut result = vec![];
+    let mut style = Style::default();
+    let mut idx = 0;
+
+    while let Some(c) = text[idx..].chars().next() {
+        let rest = &text[idx..];
+        let mut len = c.len_utf8();
+
+        match c {
+            '\\' if rest.len() > 1 => {
+                let escaped = rest[1..].chars().next().unwrap_or('\\');
+                result.push((escaped, style));
+                len += escaped.len_utf8();
+            }
+            '*' | '_' if rest.starts_with("**") || rest.starts_with("__") => {
+                style.em = !style.em;
+                len = 2;
+            }
+            '*' => {
+                style.em = !style.em;
+                if !styled {
+                    result.push(('*', style));
+                }
+            }
+            '`' => {
+                style.code = !style.code;
+                if !styled {
+                    result.push(('`', style));
+                }
+            }
+            '<' => match parse_tag(rest) {
+                Some((tag, tag_len)) => {
+                    match tag {
+                        "em" | "/em" => {
+                            style.em = tag == "em";
+                            if !styled {
+                                result.push(('*', style));
+                            }
+                        }
+                        "code" | "/code" => {
+                            style.code = tag == "code";
+                            if !styled {
+                                result.push(('`', style));
+                            }
+                        }
+                        _ => {}
+                    }
+                    len = tag_len;
+                }
+                None => result.push((c, style)),
+            },
+            '&' => match decode_entity(rest) {
+                Some((decoded, entity_len)) => {
+                    result.push((decoded, style));
+                    len = entity_len;
+                }
+                None => result.push((c, style)),
+            },
+            '[' if !style.code => {
+                // links render as their text, the target does not help in a terminal.
+                let link = rest.find("](").and_then(|mid| Some((mid, mid + rest[mid..].find(')')?)));
+                match link {
+                    Some((mid, end)) => {
+                        let text = parse_inline(&rest[1..mid], styled);
+                        result.extend(text.into_iter().map(|(c, inner)| {
+                            let link = Style {
+                                em: inner.em || style.em,
+                                code: inner.code || style.code,
+                                link: true,
+                            };
+                            (c, link)
+                        }));
+                        len = end + 1;
+                    }
+                    None => result.push((c, style)),
+                }
+            }
+            c => result.push((c, style)),
+        }
+
+        idx += len;
+    }
+
+    result
+}
+
+/// Code is taken literally, only highlights with `<em>` tags and HTML entities are interpreted.
+fn parse_code(line: &str) -> Vec<(char, Style)> {
+    let code = Style {
+        code: true,
+        ..Style::default()
+    };
+    let mut result = vec![];
+    let mut style = code;
+    let mut idx = 0;
+
+    while let Some(c) = line[idx..].chars().next() {
+        let rest = &line[idx..];
+        let mut len = c.len_utf8();
+
+        match (c, parse_tag(rest), decode_entity(rest)) {
+            ('<', Some((tag, tag_len)), _) => {
+                style.em = tag == "em" || (style.em && tag != "/em");
+                len = tag_len;
+            }
+            ('&', _, Some((decoded, entity_len))) => {
+                result.push((decoded, style));
+                len = entity_len;
+            }
+            (c, _, _) => result.push((c, style)),
+        }
+
+        idx += len;
+    }
+
+    result
+}
+
+/// Wraps styled text to `width` columns, words longer than a line are kept whole.
+fn wrap(chars: &[(char, Style)], width: usize) -> Vec<Vec<(char, Style)>> {
+    let mut lines: Vec<Vec<(char, Style)>> = vec![];
+    let mut line: Vec<(char, Style)> = vec![];
+
+    for word in chars
+        .split(|(c, _)| c.is_whitespace())
+        .filter(|word| !word.is_empty())
+    {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if let Some(&(_, previous)) = line.last() {
+            // spaces only take the style of the words around them if both share it.
+            let style = if previous == word[0].1 { previous } else { Style::default() };
+            line.push((' ', style));
+        }
+        line.extend_from_slice(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn render_line(line: &[(char, Style)], styled: bool) -> String {
+    let mut out = String::new();
+    let mut current = Style::default();
+
+    for &(c, style) in line {
+        if styled && style != current {
+            out.push_str(ANSI_RESET);
+            out.push_str(&style.ansi());
+            current = style;
+        }
+        out.push(c);
+    }
+
+    if styled && current != Style::default() {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+fn render_text(text: &str, width: usize, prefix: &str, styled: bool, out: &mut String) {
+    let indent = prefix.chars().count();
+    let chars = parse_inline(text, styled);
+
+    for (idx, line) in wrap(&chars, width.saturating_sub(indent)).iter().enumerate() {
+        if idx == 0 {
+            out.push_str(prefix);
+        } else {
+            out.push_str(&" ".repeat(indent));
+        }
+        out.push_str(&render_line(line, styled));
+        out.push('\n');
+    }
+}
+
+/// Returns whether a heading starts the second half of the puzzle.
+fn is_part_two(block: &Block) -> bool {
+    matches!(block, Block::Heading(heading) if heading.contains("Part Two"))
+}
+
+/// Renders a puzzle description for a terminal `width` columns wide. `part` limits the output to one half of
+/// the puzzle, which returns `None` for part two before it is unlocked. With `styled`, ANSI escape codes are used.
+pub fn render(markdown: &str, width: usize, part: Option<u8>, styled: bool) -> Option<String> {
+    let blocks = parse_blocks(markdown);
+    let split = blocks.iter().position(is_part_two);
+
+    let blocks = match (part, split) {
+        (Some(1), Some(split)) => &blocks[..split],
+        (Some(2), Some(split)) => &blocks[split..],
+        (Some(2), None) => return None,
+        _ => &blocks[..],
+    };
+
+    let mut out = String::new();
+
+    for (idx, block) in blocks.iter().enumerate() {
+        let is_list = |block: Option<&Block>| matches!(block, Some(Block::ListItem(_)));
+        if idx > 0 && !(is_list(Some(block)) && is_list(blocks.get(idx - 1))) {
+            out.push('\n');
+        }
+
+        match block {
+            Block::Heading(heading) => {
+                let heading: String = parse_inline(heading, false).into_iter().map(|(c, _)| c).collect();
+                if styled {
+                    out.push_str(&format!("{ANSI_HEADING}{heading}{ANSI_RESET}\n"));
+                } else {
+                    out.push_str(&format!("{heading}\n"));
+                }
+            }
+            Block::Paragraph(text) => render_text(text, width, "", styled, &mut out),
+            Block::ListItem(text) => render_text(text, width, "  • ", styled, &mut out),
+            Block::Code(lines) => {
+                // examples are often grids, so they are never wrapped.
+                for line in lines {
+                    out.push_str("    ");
+                    out.push_str(&render_line(&parse_code(line), styled));
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    Some(out)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+
+    const FIXTURE: &str = include_str!("fixtures/puzzle.md");
+
+    #[test]
+    fn renders_plain_text() {
+        let rendered = render(FIXTURE, 60, None, false).unwrap();
+
+        assert!(rendered.starts_with("--- Day 1: Trebuchet?! ---\n\nSomething is wrong with global snow production, and you've\nbeen selected"));
+        assert!(rendered.contains("\n\n    1abc2\n    pqr3stu8vwx\n"));
+        assert!(rendered.contains("Consider your entire calibration document. *What is the sum\nof all"));
+        assert!(rendered.contains("  • `two1nine` has the value `29`;\n  • `eightwothree` has `83`, so `a < b` stays.\n"));
+        assert!(rendered.lines().all(|line| line.chars().count() <= 60));
+    }
+
+    #[test]
+    fn renders_styles() {
+        let rendered = render("A *two-digit number* and `12`, `*142*`.\n\n```\n.<em>#</em>.\n```", 80, None, true).unwrap();
+
+        assert_eq!(
+            rendered,
+            "A \x1b[0m\x1b[1mtwo-digit number\x1b[0m and \x1b[0m\x1b[36m12\x1b[0m, \x1b[0m\x1b[36m\x1b[1m142\x1b[0m.\n\n    \x1b[0m\x1b[36m.\x1b[0m\x1b[36m\x1b[1m#\x1b[0m\x1b[36m.\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn renders_parts() {
+        let part_one = render(FIXTURE, 80, Some(1), false).unwrap();
+        assert!(part_one.trim_end().ends_with("Your puzzle answer was `54331`."));
+
+        let part_two = render(FIXTURE, 80, Some(2), false).unwrap();
+        assert!(part_two.starts_with("--- Part Two ---\n\nYour calculation isn't quite right."));
+
+        let (unsolved, _) = FIXTURE.split_once("\\--- Part Two").unwrap();
+        assert_eq!(render(unsolved, 80, Some(2), false), None);
+        assert!(render(unsolved, 80, Some(1), false).is_some());
+    }
+}
@@ -0,0 +1,102 @@
+/// Module that updates sections of the readme that are delimited by a pair of HTML comment markers.
+/// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::{fs, io};
+
+use crate::template::config;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+pub struct TablePosition {
+    pub pos_start: usize,
+    pub pos_end: usize,
+}
+
+/// Finds the section delimited by `marker`, including the markers themselves.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches
+        .first()
+        .map(|m| m.0)
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+
+    Ok(TablePosition { pos_start, pos_end })
+}
+
+/// Replaces the section delimited by `marker` in `s` with `content`, keeping the markers.
+pub fn update_content(s: &mut String, marker: &str, content: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    let section = format!("{marker}\n{content}\n{marker}");
+    s.replace_range(positions.pos_start..positions.pos_end, &section);
+    Ok(())
+}
+
+/// Replaces the section delimited by `marker` in the readme with `content`.
+pub fn update(marker: &str, content: &str) -> Result<(), Error> {
+    let path = &config::get().readme.path;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, marker, content)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/// Reads the content of the section delimited by `marker` from `readme`, without the markers.
+pub fn read_section<'a>(readme: &'a str, marker: &str) -> Option<&'a str> {
+    let positions = locate_table(readme, marker).ok()?;
+    readme[positions.pos_start..positions.pos_end]
+        .strip_prefix(marker)?
+        .strip_suffix(marker)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_section, update_content};
+
+    const MARKER: &str = "<!--- some table --->";
+
+    #[test]
+    fn replaces_section_between_markers() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        update_content(&mut s, MARKER, "new").unwrap();
+        assert_eq!(s, format!("foo\n{MARKER}\nnew\n{MARKER}\nbar"));
+        assert_eq!(read_section(&s, MARKER), Some("\nnew\n"));
+    }
+
+    #[test]
+    fn leaves_other_sections_alone() {
+        let other = "<!--- other table --->";
+        let mut s = format!("{MARKER}{MARKER}\n{other}\nkeep\n{other}");
+        update_content(&mut s, MARKER, "new").unwrap();
+        assert!(s.ends_with(&format!("{other}\nkeep\n{other}")));
+    }
+
+    #[test]
+    fn errors_on_missing_or_repeated_markers() {
+        assert!(update_content(&mut "# readme".into(), MARKER, "new").is_err());
+        assert!(update_content(&mut MARKER.repeat(3), MARKER, "new").is_err());
+        assert_eq!(read_section("# readme", MARKER), None);
+    }
+}
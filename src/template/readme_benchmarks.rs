@@ -1,21 +1,13 @@
 /// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
-
+use crate::template::benchmark_history::{self, chart_path, History};
+use crate::template::runner::parse_nanos;
+use crate::template::{config, readme};
 use crate::Day;
 
-static MARKER: &str = "<!--- benchmarking table --->";
-
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
+pub use crate::template::readme::Error;
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+fn marker() -> &'static str {
+    &config::get().readme.benchmarks_marker
 }
 
 #[derive(Clone)]
@@ -26,86 +18,109 @@ pub struct Timings {
     pub total_nanos: f64,
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
-
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
-
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
-    }
-
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
-
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// Renders the benchmarking table. With a `history`, the table gets a trend column and links the chart.
+fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64, history: Option<&History>) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![header, String::new()];
+    if history.is_some() {
+        lines.push("| Day | Part 1 | Part 2 | Trend |".into());
+        lines.push("| :---: | :---: | :---:  | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if let Some(history) = history {
+            let trend = history.trend(timing.day).unwrap_or_else(|| "-".into());
+            line.push_str(&format!(" {trend} |"));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+
+    if history.is_some() {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark history](./{})", chart_path()));
+    }
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+/// Reads the timings from the benchmarking table in `readme`. Days without a row are left out.
+pub fn read_timings(readme: &str) -> Vec<Timings> {
+    let Some(section) = readme::read_section(readme, marker()) else {
+        return vec![];
+    };
+
+    section
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let [_, day_cell, part_1, part_2, ..] = cells[..] else {
+                return None;
+            };
+
+            let day = day_cell
+                .strip_prefix("[Day ")?
+                .split(']')
+                .next()?
+                .parse::<u8>()
+                .ok()
+                .and_then(Day::new)?;
+
+            let timing = |cell: &str| {
+                Some(cell.trim_matches('`'))
+                    .filter(|t| parse_nanos(t).is_some())
+                    .map(String::from)
+            };
+            let (part_1, part_2) = (timing(part_1), timing(part_2));
+
+            let total_nanos = [&part_1, &part_2]
+                .into_iter()
+                .flatten()
+                .filter_map(|t| parse_nanos(t))
+                .sum();
+
+            Some(Timings { day, part_1, part_2, total_nanos })
+        })
+        .collect()
 }
 
+/// Records the timings in the benchmark history, then updates the table in the readme.
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    let history = benchmark_history::update(&timings)?;
+    readme::update(marker(), &construct_table("##", timings, total_millis, Some(&history)))
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{construct_table, read_timings, Error, Timings};
     use crate::day;
+    use crate::template::benchmark_history::History;
+    use crate::template::readme;
+
+    const MARKER: &str = "<!--- benchmarking table --->";
+
+    fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+        readme::update_content(s, MARKER, &construct_table("##", timings, total_millis, None))
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -160,6 +175,21 @@ mod tests {
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn reads_timings() {
+        let mut s = format!("foo\n{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        s = s.replace("`40ms` | `50ms`", "`40ms` | `-`");
+
+        let timings = read_timings(&s);
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[1].day, day!(2));
+        assert_eq!(timings[1].part_2.as_deref(), Some("40ms"));
+        assert_eq!(timings[2].part_2, None);
+        assert_eq!(timings[2].total_nanos, 4e+7);
+        assert!(read_timings("# readme").is_empty());
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
@@ -183,4 +213,25 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_trend() {
+        let mut history = History::default();
+        history.record("aaa", 1, &get_mock_timings());
+        let mut faster = get_mock_timings();
+        faster[0].part_1 = Some("5ms".into());
+        faster[0].part_2 = Some("10ms".into());
+        history.record("bbb", 2, &faster);
+
+        let table = construct_table("##", faster, 175.0, Some(&history));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[2], "| Day | Part 1 | Part 2 | Trend |");
+        assert_eq!(lines[4], "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | █▁ -50% |");
+        assert_eq!(lines[5], "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | ▅▅ ±0% |");
+        assert_eq!(lines.last(), Some(&"![Benchmark history](./.assets/benchmarks.svg)"));
+
+        // the trend column does not confuse the parser.
+        let s = format!("{MARKER}\n{table}\n{MARKER}");
+        assert_eq!(read_timings(&s).len(), 3);
+    }
 }
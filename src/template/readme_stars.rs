@@ -0,0 +1,140 @@
+/// Module that updates the readme with a table of collected stars.
+/// Stars come from the locally recorded answers, titles from the downloaded puzzle descriptions.
+use std::fs;
+
+use crate::template::{answers, aoc_cli, config, readme};
+use crate::{all_days, Day};
+
+pub use crate::template::readme::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub title: Option<String>,
+    pub stars: [bool; 2],
+}
+
+/// Extracts the title from a puzzle description, e.g. `Trebuchet?!` from `\--- Day 1: Trebuchet?! ---`.
+pub fn parse_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let line = line.trim_start_matches(['#', ' ', '\\']).trim();
+        let (_, title) = line
+            .strip_prefix("--- Day ")?
+            .strip_suffix("---")?
+            .split_once(':')?;
+        Some(title.trim().to_string()).filter(|title| !title.is_empty())
+    })
+}
+
+/// Collects the progress of every day that has a puzzle description or a recorded answer.
+pub fn collect() -> Vec<DayProgress> {
+    all_days()
+        .filter_map(|day| {
+            let puzzle = fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok();
+            let stars = answers::read(day).map(|answer| answer.is_some());
+
+            if puzzle.is_none() && stars == [false, false] {
+                return None;
+            }
+
+            Some(DayProgress {
+                day,
+                title: puzzle.as_deref().and_then(parse_title),
+                stars,
+            })
+        })
+        .collect()
+}
+
+fn construct_table(prefix: &str, year: Option<u16>, progress: &[DayProgress]) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        let mut name = format!("Day {}", day.day.into_inner());
+        if let Some(title) = &day.title {
+            name = format!("{name}: {}", title.replace('|', "\\|"));
+        }
+
+        if let Some(year) = year {
+            name = format!("[{name}](https://adventofcode.com/{year}/day/{})", day.day.into_inner());
+        }
+
+        let [part_1, part_2] = day.stars.map(|star| if star { "⭐" } else { " " });
+        lines.push(format!("| {name} | {part_1} | {part_2} |"));
+    }
+
+    let stars = progress.iter().flat_map(|day| day.stars).filter(|star| *star).count();
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+
+    lines.join("\n")
+}
+
+/// Renders the stars table into the readme, returns the number of collected stars.
+pub fn update() -> Result<usize, Error> {
+    let progress = collect();
+    let marker = &config::get().readme.stars_marker;
+    readme::update(marker, &construct_table("##", aoc_cli::get_year(), &progress))?;
+    Ok(progress.iter().flat_map(|day| day.stars).filter(|star| *star).count())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, parse_title, DayProgress};
+    use crate::day;
+
+    #[test]
+    fn parses_titles() {
+        let puzzle = "\\--- Day 1: Trebuchet?! ---\n----------\n\nSomething is wrong";
+        assert_eq!(parse_title(puzzle).as_deref(), Some("Trebuchet?!"));
+
+        let puzzle = "## \\--- Day 12: Hot Springs ---\n";
+        assert_eq!(parse_title(puzzle).as_deref(), Some("Hot Springs"));
+
+        assert_eq!(parse_title("# no title here"), None);
+    }
+
+    #[test]
+    fn format_stars() {
+        let progress = [
+            DayProgress {
+                day: day!(1),
+                title: Some("Trebuchet?!".into()),
+                stars: [true, true],
+            },
+            DayProgress {
+                day: day!(2),
+                title: None,
+                stars: [true, false],
+            },
+        ];
+
+        let expected = [
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1: Trebuchet?!](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "",
+            "**Stars: 3/50**",
+        ]
+        .join("\n");
+        assert_eq!(construct_table("##", Some(2023), &progress), expected);
+
+        let table = construct_table("##", None, &progress[..1]);
+        assert!(table.starts_with("## Results") && table.contains("| Day 1: Trebuchet?! | ⭐ | ⭐ |"));
+    }
+}
@@ -1,63 +1,365 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::{aoc_cli, answers, config, ANSI_ITALIC, ANSI_RESET};
+use crate::{ocr, Day};
+use itertools::Itertools;
+use std::any::Any;
+use std::error::Error;
 use std::fmt::Display;
 use std::io::{stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
 use std::process::Output;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// The outcome of a single execution of a solution part.
+pub enum Outcome<T> {
+    /// The part produced an answer.
+    Solved(T),
+    /// The part has not been solved yet (i.e. it returned [`None`]).
+    Unsolved,
+    /// The part returned an error.
+    Failed(Box<dyn Error>),
+    /// The part panicked.
+    Panicked(PanicReport),
+}
+
+/// Describes a panic raised while running a solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicReport {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+static PANIC_REPORT: Mutex<Option<PanicReport>> = Mutex::new(None);
+static CATCHING: Mutex<()> = Mutex::new(());
+
+/// Runs `func`, turning a panic into a [`PanicReport`] instead of letting it unwind further.
+/// The default panic message is suppressed for panics raised by `func`, either on the calling
+/// thread or on a rayon worker, while panics of unrelated threads are reported as usual.
+pub fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, PanicReport> {
+    fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+        mutex.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // the panic hook is global, only one caller may swap it at a time.
+    let _catching = lock(&CATCHING);
+    lock(&PANIC_REPORT).take();
+
+    let owner = thread::current().id();
+    let previous_hook = Arc::new(panic::take_hook());
+    let fallback_hook = Arc::clone(&previous_hook);
+
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().id() != owner && rayon::current_thread_index().is_none() {
+            return fallback_hook(info);
+        }
+
+        // with rayon, a panic can be followed by others. the first one is the interesting one.
+        lock(&PANIC_REPORT).get_or_insert_with(|| PanicReport {
+            message: panic_message(info.payload()),
+            location: info.location().map(ToString::to_string),
+        });
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+
+    // dropping our hook releases its handle on the previous one, so it can be reinstalled as is.
+    drop(panic::take_hook());
+    match Arc::try_unwrap(previous_hook) {
+        Ok(hook) => panic::set_hook(hook),
+        Err(hook) => panic::set_hook(Box::new(move |info| hook(info))),
+    }
+
+    result.map_err(|payload| {
+        lock(&PANIC_REPORT).take().unwrap_or_else(|| PanicReport {
+            message: panic_message(payload.as_ref()),
+            location: None,
+        })
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<dyn Any>".into()
+    }
+}
+
+/// A value that can be returned by a solution part.
+///
+/// Implemented for [`Option<T>`] (where [`None`] marks an unsolved part) and for [`Result<T, E>`],
+/// where errors are reported together with their chain of sources.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_outcome(self) -> Outcome<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Some(answer) => Outcome::Solved(answer),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(e) => Outcome::Failed(e.into()),
+        }
+    }
+}
+
+impl<T: Display> PartResult for Outcome<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> Outcome<T> {
+        self
+    }
+}
+
+/// Reads the input of a solution, either from the file passed with `--input <path>` or from `data/inputs`.
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == "--input") {
+        Some(index) => {
+            let Some(path) = args.get(index + 1) else {
+                eprintln!("Unexpected command-line input. Format: --input <path>");
+                process::exit(1);
+            };
+            fs::read_to_string(path).unwrap_or_else(|e| {
+                eprintln!("could not open input file \"{path}\": {e}");
+                process::exit(1);
+            })
+        }
+        None => super::read_file("inputs", day),
+    }
+}
+
+/// Returns the value following the flag `name` on the command line, e.g. `2` for `--part 2`.
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = env::args().skip_while(|x| x != name);
+    args.next()?;
+    args.next()
+}
+
+/// Checks whether `part` should run, i.e. no other part was selected with `--part <n>`.
+fn is_selected(part: u8) -> bool {
+    match arg_value("--part") {
+        Some(value) => value.parse::<u8>().ok() == Some(part),
+        None => true,
+    }
+}
+
+/// A named implementation of a solution part.
+///
+/// The answer is converted to a string, so variants with different return types can be compared.
+pub struct Variant<I> {
+    pub name: &'static str,
+    func: Box<dyn Fn(I) -> Outcome<String>>,
+}
+
+impl<I> Variant<I> {
+    pub fn new<R: PartResult>(name: &'static str, func: impl Fn(I) -> R + 'static) -> Self {
+        Self {
+            name,
+            func: Box::new(move |input| match func(input).into_outcome() {
+                Outcome::Solved(answer) => Outcome::Solved(answer.to_string()),
+                Outcome::Unsolved => Outcome::Unsolved,
+                Outcome::Failed(err) => Outcome::Failed(err),
+                Outcome::Panicked(report) => Outcome::Panicked(report),
+            }),
+        }
+    }
+
+    fn run(&self, input: I) -> Outcome<String> {
+        (self.func)(input)
+    }
+}
+
+/// Runs both parts of a solution, each given as a list of variants where the first one is the default.
+///
+/// `--variant <name>` runs the variant with that name instead of the default for every part that has
+/// one. `--compare-variants` runs and benchmarks every variant and checks that they agree.
+pub fn run_solution<I: Clone>(day: Day, input: I, parts: [Vec<Variant<I>>; 2]) {
+    if env::args().any(|x| x == "--compare-variants") {
+        let mut agree = true;
+        for (part, variants) in (1..).zip(&parts) {
+            if is_selected(part) {
+                agree &= compare_variants(variants, input.clone(), part);
+            }
+        }
+
+        if !agree {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let requested = arg_value("--variant");
+
+    if let Some(name) = &requested {
+        if !parts.iter().flatten().any(|variant| variant.name == name) {
+            let names = parts.iter().flatten().map(|variant| variant.name).collect::<Vec<_>>();
+            eprintln!("unknown variant \"{name}\", available variants: {}", names.join(", "));
+            process::exit(1);
+        }
+    }
+
+    for (part, variants) in (1..).zip(&parts) {
+        let chosen = requested.as_deref().and_then(|name| variants.iter().find(|v| v.name == name));
+
+        match (chosen, variants.first()) {
+            (Some(variant), _) => {
+                let label = format!("Part {part} ({})", variant.name);
+                run_labeled(|input| variant.run(input), input.clone(), day, part, &label);
+            }
+            (None, Some(variant)) => run_part(|input| variant.run(input), input.clone(), day, part),
+            (None, None) => {}
+        }
+    }
+
+    if env::args().any(|x| x == "--stats") {
+        if let Some(kib) = peak_memory() {
+            println!("Peak memory: {kib} KiB");
+        }
+    }
+}
+
+/// Benchmarks every variant of a part side by side, relative to the default one.
+/// Returns whether all of them produced the same answer.
+fn compare_variants<I: Clone>(variants: &[Variant<I>], input: I, part: u8) -> bool {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+    let mut answers: Vec<(&str, String)> = vec![];
+    let mut baseline: Option<Duration> = None;
+
+    for variant in variants {
+        let label = format!("  {:<width$}", variant.name);
+
+        let timer = Instant::now();
+        let result = catch_panic(|| variant.run(input.clone())).unwrap_or_else(Outcome::Panicked);
+        let base_time = timer.elapsed();
+
+        print!("{label}");
+        let Outcome::Solved(answer) = &result else {
+            print_result(&result, &label, " ");
+            continue;
+        };
+
+        let stats = bench(|input| variant.run(input), input.clone(), &base_time);
+        let baseline = *baseline.get_or_insert(stats.mean);
+        let ratio = stats.mean.as_secs_f64() / baseline.as_secs_f64().max(f64::EPSILON);
+
+        print!("\r");
+        println!(
+            "{label}  {ANSI_BOLD}{}{ANSI_RESET}{} {ANSI_ITALIC}{ratio:.1}x{ANSI_RESET}",
+            answer.replace('\n', " "),
+            format_duration(&stats.mean, stats.samples)
+        );
+        answers.push((variant.name, answer.clone()));
+    }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let agree = answers.iter().map(|(_, answer)| answer).all_equal();
+    if agree && answers.len() == variants.len() {
+        println!("  ✔ all variants agree");
+    } else if agree {
+        println!("  ✖ not every variant produced an answer");
+    } else {
+        println!("  ✖ variants disagree");
+    }
+
+    agree && answers.len() == variants.len()
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    run_labeled(func, input, day, part, &format!("Part {part}"));
+}
 
-    if let Some(result) = result {
+fn run_labeled<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    part_str: &str,
+) {
+    if !is_selected(part) {
+        return;
+    }
+
+    let (result, stats) = run_timed(func, input, |result| print_result(result, part_str, ""));
+
+    print_result(&result, part_str, &format_duration(&stats.mean, stats.samples));
+
+    if stats.samples > 1 && env::args().any(|x| x == "--stats") {
+        println!("{part_str} stats: {stats}");
+    }
+
+    if let Outcome::Solved(result) = result {
         submit_result(result, day, part);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///  2. in release, the function is benched (approx. the bench budget of `aoc.toml` or its minimum number of samples, whatever takes longer.)
+fn run_timed<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl Fn(&Outcome<R::Answer>),
+) -> (Outcome<R::Answer>, Stats) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = catch_panic(|| func(input.clone()).into_outcome()).unwrap_or_else(Outcome::Panicked);
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    // only successful parts are worth benching. they did not panic the first time, so the samples run without
+    // swapping the panic hook for each of them.
+    let is_solved = matches!(result, Outcome::Solved(_));
+
+    let stats = if is_solved && std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Stats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench = &config::get().bench;
+    let bench_iterations = (bench.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bench.min_samples, bench.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,19 +371,119 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Statistics over the execution times of a part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    fn from_samples(timers: &[Duration]) -> Self {
+        let sorted: Vec<f64> = timers
+            .iter()
+            .map(|t| t.as_nanos() as f64)
+            .sorted_by(f64::total_cmp)
+            .collect();
+        let count = sorted.len().max(1) as f64;
+
+        let mean = sorted.iter().sum::<f64>() / count;
+        let variance = sorted.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / count;
+        let median = match sorted.len() {
+            0 => 0_f64,
+            len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2_f64,
+            len => sorted[len / 2],
+        };
+
+        let nanos = |n: f64| Duration::from_secs_f64(n / 1e9);
+
+        Self {
+            samples: timers.len() as u128,
+            mean: nanos(mean),
+            min: nanos(sorted.first().copied().unwrap_or_default()),
+            median: nanos(median),
+            max: nanos(sorted.last().copied().unwrap_or_default()),
+            stddev: nanos(variance.sqrt()),
+        }
+    }
+
+    /// Parses a line printed with `--stats`, e.g. `Part 1 stats: mean 1.5µs, min 1.2µs, … @ 100 samples`.
+    /// Returns the part and its statistics.
+    pub fn parse(line: &str) -> Option<(u8, Self)> {
+        let (label, rest) = line.split_once(" stats: ")?;
+        let part = label.strip_prefix("Part ")?.parse::<u8>().ok()?;
+        let (values, samples) = rest.split_once(" @ ")?;
+
+        let durations: Vec<Duration> = values
+            .split(", ")
+            .map(|value| {
+                let nanos = parse_nanos(value.split_once(' ')?.1)?;
+                Some(Duration::from_secs_f64(nanos / 1e9))
+            })
+            .collect::<Option<_>>()?;
+
+        let [mean, min, median, max, stddev] = durations[..] else {
+            return None;
+        };
+
+        Some((
+            part,
+            Self {
+                samples: samples.strip_suffix(" samples")?.parse().ok()?,
+                mean,
+                min,
+                median,
+                max,
+                stddev,
+            },
+        ))
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:.1?}, min {:.1?}, median {:.1?}, max {:.1?}, stddev {:.1?} @ {} samples",
+            self.mean, self.min, self.median, self.max, self.stddev, self.samples
+        )
+    }
+}
+
+/// Reads the peak resident set size of the current process in KiB. Only available on Linux.
+pub fn peak_memory() -> Option<u64> {
+    fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parses a duration as printed by [`Debug`] for [`Duration`] (e.g. `74.13ns`, `1.2ms` or `2s`) into nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.ends_with("ns") => s.strip_suffix("ns")?.parse::<f64>().ok(),
+        s if s.ends_with("µs") => s.strip_suffix("µs")?.parse::<f64>().ok().map(|x| x * 1000_f64),
+        s if s.ends_with("ms") => s.strip_suffix("ms")?.parse::<f64>().ok().map(|x| x * 1_000_000_f64),
+        s => s.strip_suffix('s')?.parse::<f64>().ok().map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Formats nanoseconds like [`Debug`] does for [`Duration`], the inverse of [`parse_nanos`].
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -92,11 +494,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -105,6 +507,9 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     print!("\r");
                     println!("{str}");
                     println!("{result}");
+                    if let Some(letters) = ocr(&result.to_string()) {
+                        println!("{part} letters: {ANSI_BOLD}{letters}{ANSI_RESET}");
+                    }
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
@@ -116,7 +521,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -124,6 +529,37 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(err) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ error       ");
+                print_error_chain(err.as_ref());
+            }
+        }
+        Outcome::Panicked(report) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ panicked    ");
+                let _ = stdout().flush();
+                eprintln!("{ANSI_BOLD}error:{ANSI_RESET} {report}");
+            }
+        }
+    }
+}
+
+/// Prints an error followed by each of its sources.
+fn print_error_chain(err: &dyn Error) {
+    let _ = stdout().flush();
+    eprintln!("{ANSI_BOLD}error:{ANSI_RESET} {err}");
+
+    let mut source = err.source();
+    while let Some(cause) = source {
+        eprintln!("  {ANSI_ITALIC}caused by:{ANSI_RESET} {cause}");
+        source = cause.source();
     }
 }
 
@@ -162,6 +598,51 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    // letters drawn as pixels are submitted as the text they show.
+    let result = result.to_string();
+    let result = ocr(&result).filter(|_| result.contains('\n')).unwrap_or(result);
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let output = aoc_cli::submit(day, part, &result);
+
+    if let Ok(output) = &output {
+        if aoc_cli::is_accepted(output) {
+            match answers::record(day, part, &result) {
+                Ok(()) => println!("🎄 Recorded answer for part {part}."),
+                Err(e) => eprintln!("failed to record answer: {e}"),
+            }
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    #[test]
+    fn computes_stats() {
+        let timers = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&timers);
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.max, Duration::from_micros(4));
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = Stats::from_samples(&[10, 20, 60].map(Duration::from_millis));
+        let line = format!("Part 2 stats: {stats}");
+        assert_eq!(line, "Part 2 stats: mean 30.0ms, min 10.0ms, median 20.0ms, max 60.0ms, stddev 21.6ms @ 3 samples");
+
+        let (part, parsed) = Stats::parse(&line).unwrap();
+        assert_eq!(part, 2);
+        assert_eq!((parsed.samples, parsed.median), (3, Duration::from_millis(20)));
+        assert!(Stats::parse("Part 1: 42 (1.0ms)").is_none());
+    }
 }
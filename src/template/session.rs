@@ -0,0 +1,323 @@
+/// Management of the Advent of Code session token.
+///
+/// The token is stored in the file configured as `session.token_file` in `aoc.toml`, by default
+/// `~/.adventofcode.session` where aoc-cli looks for it as well. Requests to the server configured as
+/// `session.server` are made with `curl`, the token is passed on stdin so it does not show up in the
+/// process list.
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::template::config;
+
+const DEFAULT_TOKEN_FILE: &str = "~/.adventofcode.session";
+const USER_AGENT: &str =
+    "advent_of_code rust template (https://github.com/fspoettel/advent-of-code-rust)";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The token does not look like a session token.
+    InvalidFormat(String),
+    /// No token has been stored yet.
+    Missing(PathBuf),
+    /// The server does not accept the token, most likely because it expired.
+    Expired,
+    /// The server could not be reached or answered with an unexpected response.
+    Request(String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidFormat(reason) => write!(f, "invalid session token: {reason}"),
+            Error::Missing(path) => write!(
+                f,
+                "no session token found at \"{}\". Run \"cargo aoc login\" to store one.",
+                path.display()
+            ),
+            Error::Expired => write!(
+                f,
+                "the session token was rejected by {}, it has probably expired. Log in on the website again and run \"cargo aoc login\" with the new token.",
+                config::get().session.server
+            ),
+            Error::Request(message) => write!(f, "request failed: {message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Returns the file the token is stored in.
+pub fn token_path() -> PathBuf {
+    let path = config::get()
+        .session
+        .token_file
+        .as_deref()
+        .unwrap_or(DEFAULT_TOKEN_FILE);
+    PathBuf::from(config::expand_home(path))
+}
+
+/// Normalizes a pasted token and checks that it looks like a session token, i.e. a long hexadecimal string.
+/// The `session=` prefix of a copied cookie is removed.
+pub fn parse_token(token: &str) -> Result<String, Error> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    if token.is_empty() {
+        return Err(Error::InvalidFormat("the token is empty".into()));
+    }
+
+    if let Some(c) = token.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidFormat(format!(
+            "unexpected character {c:?}, the token consists of hexadecimal digits only"
+        )));
+    }
+
+    if token.len() < 96 {
+        return Err(Error::InvalidFormat(format!(
+            "the token is {} characters long, expected at least 96. Did you copy all of it?",
+            token.len()
+        )));
+    }
+
+    Ok(token.to_ascii_lowercase())
+}
+
+/// Reads the stored token.
+pub fn read() -> Result<String, Error> {
+    read_from(&token_path())
+}
+
+fn read_from(path: &Path) -> Result<String, Error> {
+    match fs::read_to_string(path) {
+        Ok(token) => parse_token(&token),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::Missing(path.to_path_buf())),
+        Err(e) => Err(Error::IO(e)),
+    }
+}
+
+/// Stores the token so only the current user can read it, returns the path of the file.
+pub fn store(token: &str) -> Result<PathBuf, Error> {
+    let path = token_path();
+    store_at(&path, token)?;
+    Ok(path)
+}
+
+fn store_at(path: &Path, token: &str) -> io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to new files, restrict existing ones as well.
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(token.as_bytes())
+}
+
+/// Deletes the stored token, returns the path of the file if there was one.
+pub fn remove() -> io::Result<Option<PathBuf>> {
+    let path = token_path();
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(Some(path)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A response of the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Requests `path` (e.g. `/settings`) from the configured server with the given token.
+/// Redirects are not followed, the server redirects requests that are not logged in.
+pub fn fetch_with(token: &str, path: &str) -> Result<Response, Error> {
+    let url = format!(
+        "{}{path}",
+        config::get().session.server.trim_end_matches('/')
+    );
+
+    let mut child = Command::new("curl")
+        .args([
+            "--config",
+            "-",
+            "--silent",
+            "--show-error",
+            "--max-time",
+            "15",
+        ])
+        .args(["--write-out", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| Error::Request("command \"curl\" not found or not callable.".into()))?;
+
+    let curl_config =
+        format!("url = \"{url}\"\ncookie = \"session={token}\"\nuser-agent = \"{USER_AGENT}\"\n");
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| Error::Request("could not pass the request to curl".into()))?
+        .write_all(curl_config.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Error::Request(format!("{url}: {message}")));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+
+    Ok(Response {
+        status: status
+            .trim()
+            .parse()
+            .map_err(|_| Error::Request(format!("{url}: unexpected response")))?,
+        body: body.to_string(),
+    })
+}
+
+/// Requests `path` from the configured server with the stored token.
+pub fn fetch(path: &str) -> Result<Response, Error> {
+    fetch_with(&read()?, path)
+}
+
+/// Extracts the user name from a page of the website, it is shown in the header for logged in users.
+pub fn parse_user(response: &Response) -> Result<String, Error> {
+    if matches!(response.status, 301 | 302 | 303 | 400 | 401 | 403) {
+        return Err(Error::Expired);
+    }
+
+    if response.status != 200 {
+        return Err(Error::Request(format!(
+            "unexpected status {}",
+            response.status
+        )));
+    }
+
+    let Some((_, rest)) = response.body.split_once(r#"<div class="user">"#) else {
+        return Err(Error::Expired);
+    };
+
+    let name = rest.split(['<']).next().unwrap_or_default().trim();
+    if name.is_empty() {
+        return Err(Error::Request(
+            "could not find the user name in the response".into(),
+        ));
+    }
+
+    Ok(name
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">"))
+}
+
+/// Checks the token against the configured server, returns the name of the user it belongs to.
+pub fn check(token: &str) -> Result<String, Error> {
+    parse_user(&fetch_with(token, "/settings")?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_token, parse_user, read_from, store_at, Error, Response};
+    use std::fs;
+
+    const TOKEN: &str = "53616c7465645f5f0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn validates_token_format() {
+        assert_eq!(parse_token(&format!("  {TOKEN}\n")).unwrap(), TOKEN);
+        assert_eq!(
+            parse_token(&format!("session={}", TOKEN.to_uppercase())).unwrap(),
+            TOKEN
+        );
+
+        let reason = |token: &str| match parse_token(token) {
+            Err(Error::InvalidFormat(reason)) => reason,
+            other => panic!("expected an invalid format, got {other:?}"),
+        };
+        assert_eq!(reason(""), "the token is empty");
+        assert!(reason(&format!("{TOKEN};")).starts_with("unexpected character ';'"));
+        assert!(reason(&TOKEN[..40]).starts_with("the token is 40 characters long"));
+    }
+
+    #[test]
+    fn parses_user() {
+        let page = |status: u16, body: &str| Response {
+            status,
+            body: body.into(),
+        };
+
+        let body = r#"<header><div class="user">Jane &amp; Co <span class="star-count">42*</span></div></header>"#;
+        assert_eq!(parse_user(&page(200, body)).unwrap(), "Jane & Co");
+
+        let body = r#"<div class="user">(anonymous user #123456) <span class="star-count">2*</span></div>"#;
+        assert_eq!(
+            parse_user(&page(200, body)).unwrap(),
+            "(anonymous user #123456)"
+        );
+
+        let logged_out = r#"<header><a href="/auth/login">[Log In]</a></header>"#;
+        assert!(matches!(
+            parse_user(&page(200, logged_out)),
+            Err(Error::Expired)
+        ));
+        assert!(matches!(parse_user(&page(302, "")), Err(Error::Expired)));
+        assert!(matches!(parse_user(&page(500, "")), Err(Error::Request(_))));
+    }
+
+    #[test]
+    fn stores_token_privately() {
+        let dir = std::env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        let path = dir.join("nested").join("session");
+
+        assert!(matches!(read_from(&path), Err(Error::Missing(_))));
+
+        store_at(&path, TOKEN).unwrap();
+        store_at(&path, TOKEN).unwrap();
+        assert_eq!(read_from(&path).unwrap(), TOKEN);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(
+                fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+/// Unlock times of the puzzles, which unlock at midnight US-Eastern (UTC-5) from the 1st to the 25th of December.
+/// Waiting goes through a `Clock`, so it can be tested without actually waiting.
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{all_days, Day};
+
+pub trait Clock {
+    /// Returns the current unix timestamp in seconds.
+    fn now(&self) -> i64;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Returns the unix timestamp at which the puzzle of a day unlocks.
+pub fn unlock_timestamp(year: u16, day: Day) -> i64 {
+    // days since 1970-01-01 of the civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let (y, m, d) = (i64::from(year), 12, i64::from(day.into_inner()));
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    days * 86_400 + 5 * 3_600
+}
+
+/// Formats a number of seconds, e.g. `01:02:03` or `2d 01:02:03`.
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    match seconds / 86_400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+/// Returns the puzzle to prepare next with its unlock time: the one that unlocked in the last 24 hours if it is
+/// not prepared yet, the next one to unlock otherwise. Returns `None` once the last puzzle of the year is out.
+pub fn next_puzzle(year: u16, now: i64, is_prepared: impl Fn(Day) -> bool) -> Option<(Day, i64)> {
+    all_days()
+        .map(|day| (day, unlock_timestamp(year, day)))
+        .find(|&(day, unlock)| unlock > now || (now - unlock < 86_400 && !is_prepared(day)))
+}
+
+/// Waits until `timestamp`, calling `on_tick` with the remaining seconds about once a second.
+pub fn wait_until(clock: &impl Clock, timestamp: i64, mut on_tick: impl FnMut(i64)) {
+    loop {
+        let remaining = timestamp - clock.now();
+        if remaining <= 0 {
+            break;
+        }
+
+        on_tick(remaining);
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, next_puzzle, unlock_timestamp, wait_until, Clock};
+    use crate::day;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    /// Clock that advances when it is asked to sleep.
+    struct MockClock {
+        now: Cell<i64>,
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> i64 {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration.as_secs() as i64);
+        }
+    }
+
+    // 2023-12-01T05:00:00Z
+    const DAY_1: i64 = 1_701_406_800;
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(unlock_timestamp(2023, day!(1)), DAY_1);
+        assert_eq!(unlock_timestamp(2023, day!(2)), DAY_1 + 86_400);
+        assert_eq!(unlock_timestamp(2015, day!(25)), 1_451_019_600);
+
+        assert_eq!(format_duration(3_723), "01:02:03");
+        assert_eq!(format_duration(2 * 86_400 + 59), "2d 00:00:59");
+        assert_eq!(format_duration(-5), "00:00:00");
+    }
+
+    #[test]
+    fn finds_next_puzzle() {
+        let prepared = |_| true;
+        let unprepared = |_| false;
+
+        // before the event and right before an unlock.
+        assert_eq!(next_puzzle(2023, DAY_1 - 30 * 86_400, prepared), Some((day!(1), DAY_1)));
+        assert_eq!(next_puzzle(2023, DAY_1 + 86_399, prepared), Some((day!(2), DAY_1 + 86_400)));
+
+        // a puzzle that unlocked a minute ago is prepared right away, unless that happened already.
+        assert_eq!(next_puzzle(2023, DAY_1 + 60, unprepared), Some((day!(1), DAY_1)));
+        assert_eq!(next_puzzle(2023, DAY_1 + 60, prepared), Some((day!(2), DAY_1 + 86_400)));
+
+        assert_eq!(next_puzzle(2023, DAY_1 + 24 * 86_400 + 60, unprepared), Some((day!(25), DAY_1 + 24 * 86_400)));
+        assert_eq!(next_puzzle(2023, DAY_1 + 25 * 86_400, unprepared), None);
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = MockClock { now: Cell::new(DAY_1 - 3) };
+        let mut ticks = vec![];
+
+        wait_until(&clock, DAY_1, |remaining| ticks.push(remaining));
+        assert_eq!(ticks, [3, 2, 1]);
+        assert_eq!(clock.now(), DAY_1);
+
+        wait_until(&clock, DAY_1 - 10, |_| panic!("should not wait for the past"));
+    }
+}